@@ -3,6 +3,7 @@ extern crate criterion;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use reed_solomon_32::{Buffer, correct, encode};
+use reed_solomon_32::decoder::DECODER_8;
 
 struct TestEncode {
     ecc: u8,
@@ -24,6 +25,20 @@ fn test_decode(params: TestDecode) -> Buffer {
     correct(&params.encoded[..params.encoded_len], params.ecc, None).unwrap()
 }
 
+struct TestErasures {
+    encoded: [u8; 31],
+    encoded_len: usize,
+    erase_pos: [u8; 8],
+}
+
+fn test_decode_erasures(params: TestErasures) -> Buffer {
+    DECODER_8.correct(&params.encoded[..params.encoded_len], Some(&params.erase_pos)).unwrap()
+}
+
+fn test_recover_erasures(params: TestErasures) -> Buffer {
+    DECODER_8.recover_erasures(&params.encoded[..params.encoded_len], &params.erase_pos).unwrap()
+}
+
 pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("encode", |b| b.iter(|| test_encode(black_box(TestEncode {
         ecc: 8,
@@ -45,6 +60,16 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         encoded: [31, 31, 31, 31, 4, 5, 6, 7, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 4, 14, 24, 6, 5, 1, 8, 24, 0, 0, 0],
         encoded_len: 28,
     }))));
+    c.bench_function("decode-max-erasures", |b| b.iter(|| test_decode_erasures(black_box(TestErasures {
+        encoded: [31, 31, 31, 31, 31, 31, 31, 31, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 4, 14, 24, 6, 5, 1, 8, 24, 0, 0, 0],
+        encoded_len: 28,
+        erase_pos: [0, 1, 2, 3, 4, 5, 6, 7],
+    }))));
+    c.bench_function("recover-max-erasures", |b| b.iter(|| test_recover_erasures(black_box(TestErasures {
+        encoded: [31, 31, 31, 31, 31, 31, 31, 31, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 4, 14, 24, 6, 5, 1, 8, 24, 0, 0, 0],
+        encoded_len: 28,
+        erase_pos: [0, 1, 2, 3, 4, 5, 6, 7],
+    }))));
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,197 @@
+//! Compile time companion to the [`reed-solomon-32`](https://docs.rs/reed-solomon-32/) crate.
+//!
+//! The [`rs32!`] macro takes a base32 string literal (RFC 4648 alphabet, case-insensitive)
+//! and a number of ECC symbols and expands to a `[u8; N]` array literal. The encoding
+//! and verification is done by `reed-solomon-32` itself while the macro is being
+//! expanded, so nothing but the resulting array ends up in the final binary.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32_macros::rs32;
+//!
+//! // Encode data symbols and append 4 ECC symbols
+//! const CODEWORD: [u8; 8] = rs32!("ABCD", 4);
+//!
+//! // Check that a hand-maintained codeword is still valid
+//! const CHECKED: [u8; 8] = rs32!(verify "ABCDKJY3", 4);
+//!
+//! assert_eq!(CODEWORD, CHECKED);
+//! ```
+//!
+//! A corrupted literal is reported as a compile error that names the positions
+//! which are wrong:
+//! ```compile_fail
+//! use reed_solomon_32_macros::rs32;
+//!
+//! const CHECKED: [u8; 8] = rs32!(verify "ABCEKJY3", 4);
+//! ```
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use reed_solomon_32::alphabet::RFC4648;
+
+enum Mode {
+    Encode,
+    Verify,
+}
+
+struct Input {
+    mode: Mode,
+    symbols: Vec<u8>,
+    literal_span: Span,
+    ecc: u8,
+}
+
+/// Encodes or verifies a base32 codeword literal at compile time.
+///
+/// * `rs32!("DATA", ecc)` or `rs32!(encode "DATA", ecc)` - treats the literal as
+///   data symbols, encodes them with `ecc` error correcting symbols and expands
+///   to the whole codeword.
+/// * `rs32!(verify "CODEWORD", ecc)` - treats the literal as a complete codeword
+///   with `ecc` error correcting symbols. If it is valid, expands to the codeword.
+///   Otherwise, compilation fails with an error listing the corrupted positions
+///   along with the corrected literal, if the codeword could be repaired.
+#[proc_macro]
+pub fn rs32(input: TokenStream) -> TokenStream {
+    match parse_input(input).and_then(expand) {
+        Ok(output) => output,
+        Err((span, msg)) => compile_error(span, &msg),
+    }
+}
+
+fn expand(input: Input) -> Result<TokenStream, (Span, String)> {
+    match input.mode {
+        Mode::Encode => {
+            let encoded = reed_solomon_32::encode(&input.symbols, input.ecc)
+                .map_err(|err| (input.literal_span, err.to_string()))?;
+            Ok(array_literal(&encoded[..]))
+        }
+        Mode::Verify => {
+            let (corrected, err_count) = reed_solomon_32::correct_err_count(&input.symbols, input.ecc, None)
+                .map_err(|err| (input.literal_span, format!("Invalid codeword: {}", err)))?;
+            if err_count == 0 {
+                return Ok(array_literal(&input.symbols));
+            }
+
+            let positions: Vec<usize> = input.symbols.iter()
+                .zip(corrected.iter())
+                .enumerate()
+                .filter(|(_, (a, b))| a != b)
+                .map(|(i, _)| i)
+                .collect();
+            let fixed = corrected.to_string_with(&RFC4648);
+
+            Err((input.literal_span, format!(
+                "Codeword is corrupted at positions {:?}; the corrected codeword is \"{}\"",
+                positions,
+                fixed,
+            )))
+        }
+    }
+}
+
+fn parse_input(input: TokenStream) -> Result<Input, (Span, String)> {
+    let mut tokens = input.into_iter().peekable();
+
+    let mode = match tokens.peek() {
+        Some(TokenTree::Ident(ident)) => {
+            let mode = match ident.to_string().as_str() {
+                "encode" => Mode::Encode,
+                "verify" => Mode::Verify,
+                other => return Err((ident.span(), format!("Unknown mode `{}`, expected `encode` or `verify`", other))),
+            };
+            tokens.next();
+            mode
+        }
+        _ => Mode::Encode,
+    };
+
+    let (symbols, literal_span) = match tokens.next() {
+        Some(TokenTree::Literal(lit)) => (parse_base32(&lit)?, lit.span()),
+        Some(other) => return Err((other.span(), "Expected a base32 string literal".to_string())),
+        None => return Err((Span::call_site(), "Expected a base32 string literal".to_string())),
+    };
+
+    match tokens.next() {
+        Some(TokenTree::Punct(ref p)) if p.as_char() == ',' => {}
+        Some(other) => return Err((other.span(), "Expected `,`".to_string())),
+        None => return Err((Span::call_site(), "Expected `,` followed by the number of ECC symbols".to_string())),
+    }
+
+    let ecc = match tokens.next() {
+        Some(TokenTree::Literal(lit)) => parse_ecc(&lit)?,
+        Some(other) => return Err((other.span(), "Expected the number of ECC symbols".to_string())),
+        None => return Err((Span::call_site(), "Expected the number of ECC symbols".to_string())),
+    };
+
+    // Allow a trailing comma
+    if let Some(TokenTree::Punct(ref p)) = tokens.peek() {
+        if p.as_char() == ',' {
+            tokens.next();
+        }
+    }
+    if let Some(extra) = tokens.next() {
+        return Err((extra.span(), "Unexpected token".to_string()));
+    }
+
+    Ok(Input { mode, symbols, literal_span, ecc })
+}
+
+fn parse_base32(lit: &Literal) -> Result<Vec<u8>, (Span, String)> {
+    let repr = lit.to_string();
+    if repr.len() < 2 || !repr.starts_with('"') || !repr.ends_with('"') {
+        return Err((lit.span(), "Expected a base32 string literal".to_string()));
+    }
+
+    repr[1..repr.len() - 1]
+        .chars()
+        .map(|c| {
+            RFC4648.from_char(c)
+                .ok_or_else(|| (lit.span(), format!("Invalid base32 character {:?}", c)))
+        })
+        .collect()
+}
+
+const INTEGER_SUFFIXES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+fn parse_ecc(lit: &Literal) -> Result<u8, (Span, String)> {
+    let repr = lit.to_string();
+    let digits = INTEGER_SUFFIXES.iter()
+        .find_map(|suffix| repr.strip_suffix(suffix))
+        .unwrap_or(&repr)
+        .replace('_', "");
+    digits.parse::<u8>()
+        .map_err(|_| (lit.span(), "Expected the number of ECC symbols".to_string()))
+}
+
+fn array_literal(symbols: &[u8]) -> TokenStream {
+    let mut items = Vec::with_capacity(symbols.len() * 2);
+    for &x in symbols {
+        items.push(TokenTree::Literal(Literal::u8_suffixed(x)));
+        items.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+    }
+    let array = Group::new(Delimiter::Bracket, items.into_iter().collect());
+    TokenStream::from(TokenTree::Group(array))
+}
+
+fn compile_error(span: Span, msg: &str) -> TokenStream {
+    let mut message = Literal::string(msg);
+    message.set_span(span);
+    let mut args = Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Literal(message)));
+    args.set_span(span);
+
+    let tokens = vec![
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct({
+            let mut p = Punct::new('!', Spacing::Alone);
+            p.set_span(span);
+            p
+        }),
+        TokenTree::Group(args),
+    ];
+    tokens.into_iter().collect()
+}
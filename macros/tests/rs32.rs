@@ -0,0 +1,39 @@
+extern crate reed_solomon_32;
+extern crate reed_solomon_32_macros;
+
+use reed_solomon_32::{encode, is_corrupted};
+use reed_solomon_32_macros::rs32;
+
+#[test]
+fn encode_literal() {
+    const ENCODED: [u8; 11] = rs32!("HELLO", 6);
+
+    let expected = encode(&[7, 4, 11, 11, 14], 6).unwrap();
+    assert_eq!(&expected[..], &ENCODED[..]);
+}
+
+#[test]
+fn encode_literal_lowercase() {
+    assert_eq!(rs32!(encode "hello", 6), rs32!("HELLO", 6));
+}
+
+#[test]
+fn verify_literal() {
+    const VERIFIED: [u8; 11] = rs32!(verify "HELLONAQSEG", 6);
+
+    assert_eq!(rs32!("HELLO", 6), VERIFIED);
+    assert!(!is_corrupted(&VERIFIED, 6).unwrap());
+}
+
+#[test]
+fn no_ecc() {
+    assert_eq!([0u8, 1, 31], rs32!("AB7", 0));
+}
+
+#[test]
+fn suffixed_ecc() {
+    assert_eq!(rs32!("HELLO", 6), rs32!("HELLO", 6u8));
+    assert_eq!(rs32!("HELLO", 6), rs32!("HELLO", 6_u16));
+    assert_eq!(rs32!("HELLO", 6), rs32!("HELLO", 6i32));
+    assert_eq!(rs32!("HELLO", 10), rs32!("HELLO", 1_0usize));
+}
@@ -0,0 +1,332 @@
+//! Text representations of symbols.
+//!
+//! Each [`Alphabet`] maps the 32 symbol values to printable characters. Parsing
+//! is case-insensitive and characters that aren't part of the alphabet don't
+//! cause an error. Instead, their positions are returned as erasures that can
+//! be passed directly to [`correct`](crate::correct).
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::{correct, encode};
+//! use reed_solomon_32::alphabet::{parse_with, CROCKFORD};
+//!
+//! let encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+//! let text = encoded.display_with(&CROCKFORD).to_string();
+//! assert_eq!("1234SHF3", text);
+//!
+//! // 'I' is an alias for '1' and '!' isn't part of the alphabet
+//! let parsed = parse_with(&CROCKFORD, "i23!SHF3").unwrap();
+//! assert_eq!(Some(&[3u8][..]), parsed.erasures());
+//!
+//! let corrected = correct(parsed.symbols(), 4, parsed.erasures()).unwrap();
+//! assert_eq!(&[1, 2, 3, 4], corrected.data());
+//! ```
+
+use core::fmt;
+use crate::err::{invalid_data_len, UsageError};
+
+const INVALID: u8 = 0xff;
+
+// TODO: Make this a regular assert!() once panics in const
+//       functions are allowed: https://rust-lang.github.io/rfcs/2345-const-panic.html
+#[allow(unconditional_panic, clippy::no_effect, clippy::out_of_bounds_indexing)]
+const fn const_panic(msg: &'static str) {
+    [msg][1000];
+}
+
+/// Mapping between symbols and characters
+#[derive(Copy, Clone)]
+pub struct Alphabet {
+    encode: [u8; 32],
+    decode: [u8; 128],
+    // Bit c is set if the ASCII character c is confusable
+    confusable: u128,
+}
+
+/// The base32 alphabet from RFC 4648: `A-Z` followed by `2-7`
+///
+/// `I` and `L`, `2` and `Z`, `5` and `S`, `6` and `G` and `U` and `V` are
+/// marked as confusable.
+pub const RFC4648: Alphabet = Alphabet::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567")
+    .with_confusables(b"IL2Z5S6GUV");
+
+/// Crockford's base32 alphabet, which accepts `O` for `0` and `I` and `L` for `1`
+///
+/// The aliases are marked as confusable, as are `2` and `Z`, `5` and `S`, `6`
+/// and `G`, `8` and `B` and `V`, which is easily confused with the excluded `U`.
+pub const CROCKFORD: Alphabet = Alphabet::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ")
+    .with_alias(b'O', b'0')
+    .with_alias(b'I', b'1')
+    .with_alias(b'L', b'1')
+    .with_confusables(b"OIL2Z5S6G8BV");
+
+/// The z-base-32 alphabet
+///
+/// `1` and `i`, `5` and `s`, `9` and `g` and `6`, `8` and `b` are marked as
+/// confusable.
+pub const ZBASE32: Alphabet = Alphabet::new(b"ybndrfg8ejkmcpqxot1uwisza345h769")
+    .with_confusables(b"1i5s9g68b");
+
+/// The alphabet used by bech32
+///
+/// `2` and `z`, `5` and `s`, `9` and `g` and `u` and `v` are marked as
+/// confusable.
+pub const BECH32: Alphabet = Alphabet::new(b"qpzry9x8gf2tvdw0s3jn54khce6mua7l")
+    .with_confusables(b"2z5s9guv");
+
+impl Alphabet {
+    /// Create alphabet where the symbol `i` is represented by `chars[i]`.
+    ///
+    /// Characters are matched case-insensitively when parsing.
+    ///
+    /// # Panics
+    /// If any of the characters is not ASCII or if any of them appear twice.
+    pub const fn new(chars: &[u8; 32]) -> Alphabet {
+        let mut alphabet = Alphabet {
+            encode: *chars,
+            decode: [INVALID; 128],
+            confusable: 0,
+        };
+        let mut i = 0;
+        while i < 32 {
+            if !chars[i].is_ascii() {
+                const_panic("Alphabet characters must be ASCII");
+            }
+            if alphabet.decode[chars[i] as usize] != INVALID {
+                const_panic("Alphabet characters must be unique");
+            }
+            alphabet = alphabet.insert(chars[i], i as u8);
+            i += 1;
+        }
+        alphabet
+    }
+
+    /// Accept the character `alias` as another representation of the
+    /// character `target` when parsing.
+    ///
+    /// # Panics
+    /// If `alias` is not ASCII or is already in use, or if `target` isn't part of the alphabet.
+    pub const fn with_alias(self, alias: u8, target: u8) -> Alphabet {
+        if !alias.is_ascii() || !target.is_ascii() {
+            const_panic("Alphabet characters must be ASCII");
+        }
+        if self.decode[alias as usize] != INVALID {
+            const_panic("Alphabet characters must be unique");
+        }
+        let symbol = self.decode[target as usize];
+        if symbol == INVALID {
+            const_panic("Alias target must be part of the alphabet");
+        }
+        self.insert(alias, symbol)
+    }
+
+    /// Mark the characters in `chars` as easily confused with other characters
+    /// of the alphabet when written down or read out.
+    ///
+    /// This doesn't change how text is parsed, but [`ident`](crate::ident)
+    /// treats confusable characters as hints of where the errors might be.
+    ///
+    /// # Panics
+    /// If any of the characters is not ASCII.
+    pub const fn with_confusables(mut self, chars: &[u8]) -> Alphabet {
+        let mut i = 0;
+        while i < chars.len() {
+            if !chars[i].is_ascii() {
+                const_panic("Alphabet characters must be ASCII");
+            }
+            self.confusable |= 1 << chars[i].to_ascii_lowercase();
+            self.confusable |= 1 << chars[i].to_ascii_uppercase();
+            i += 1;
+        }
+        self
+    }
+
+    const fn insert(mut self, c: u8, symbol: u8) -> Alphabet {
+        self.decode[c.to_ascii_lowercase() as usize] = symbol;
+        self.decode[c.to_ascii_uppercase() as usize] = symbol;
+        self
+    }
+
+    /// Character that represents `symbol`.
+    ///
+    /// # Panics
+    /// If `symbol` is greater than 31.
+    pub fn to_char(&self, symbol: u8) -> char {
+        self.encode[symbol as usize] as char
+    }
+
+    /// Symbol that is represented by `c`, if `c` is part of the alphabet.
+    pub fn from_char(&self, c: char) -> Option<u8> {
+        if !c.is_ascii() {
+            return None;
+        }
+        match self.decode[c as usize] {
+            INVALID => None,
+            x => Some(x),
+        }
+    }
+
+    /// Returns `true` if `c` was marked as confusable with
+    /// [`with_confusables`](Alphabet::with_confusables).
+    pub fn is_confusable(&self, c: char) -> bool {
+        c.is_ascii() && self.confusable & 1 << c as u32 != 0
+    }
+
+    /// Returns a value that formats `symbols` using this alphabet.
+    ///
+    /// # Panics
+    /// When formatted, if any of the symbols is greater than 31.
+    pub fn display<'a>(&'a self, symbols: &'a [u8]) -> Display<'a> {
+        Display {
+            alphabet: self,
+            symbols,
+        }
+    }
+}
+
+impl fmt::Debug for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Alphabet({:?})", core::str::from_utf8(&self.encode).unwrap_or_default())
+    }
+}
+
+/// Formats symbols using an [`Alphabet`]
+#[derive(Debug, Copy, Clone)]
+pub struct Display<'a> {
+    alphabet: &'a Alphabet,
+    symbols: &'a [u8],
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &symbol in self.symbols {
+            fmt::Write::write_char(f, self.alphabet.to_char(symbol))?;
+        }
+        Ok(())
+    }
+}
+
+/// Symbols parsed from text by [`parse_with`]
+#[derive(Debug, Copy, Clone)]
+pub struct Parsed {
+    symbols: [u8; crate::POLYNOMIAL_MAX_LENGTH],
+    len: usize,
+    erasures: [u8; crate::POLYNOMIAL_MAX_LENGTH],
+    erasure_count: usize,
+}
+
+impl Parsed {
+    /// Parsed symbols. Characters that aren't part of the alphabet are
+    /// represented by 0.
+    pub fn symbols(&self) -> &[u8] {
+        &self.symbols[..self.len]
+    }
+
+    /// Positions of the characters that aren't part of the alphabet, or `None`
+    /// if all of them were.
+    pub fn erasures(&self) -> Option<&[u8]> {
+        if self.erasure_count == 0 {
+            None
+        } else {
+            Some(&self.erasures[..self.erasure_count])
+        }
+    }
+}
+
+/// Parses `text` using `alphabet`.
+///
+/// Characters that aren't part of the alphabet are returned as erasures
+/// instead of causing an error. An error is only returned if `text` is
+/// longer than 31 characters.
+pub fn parse_with(alphabet: &Alphabet, text: &str) -> Result<Parsed, UsageError> {
+    let mut parsed = Parsed {
+        symbols: [0; crate::POLYNOMIAL_MAX_LENGTH],
+        len: 0,
+        erasures: [0; crate::POLYNOMIAL_MAX_LENGTH],
+        erasure_count: 0,
+    };
+
+    for c in text.chars() {
+        if parsed.len == parsed.symbols.len() {
+            return Err(invalid_data_len());
+        }
+        match alphabet.from_char(c) {
+            Some(symbol) => parsed.symbols[parsed.len] = symbol,
+            None => {
+                parsed.erasures[parsed.erasure_count] = parsed.len as u8;
+                parsed.erasure_count += 1;
+            }
+        }
+        parsed.len += 1;
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn round_trip() {
+        let mut symbols = [0u8; 32];
+        for (i, x) in symbols.iter_mut().enumerate() {
+            *x = i as u8;
+        }
+        for alphabet in &[RFC4648, CROCKFORD, ZBASE32, BECH32] {
+            for &symbol in symbols.iter() {
+                let c = alphabet.to_char(symbol);
+                assert_eq!(Some(symbol), alphabet.from_char(c));
+                assert_eq!(Some(symbol), alphabet.from_char(c.to_ascii_lowercase()));
+                assert_eq!(Some(symbol), alphabet.from_char(c.to_ascii_uppercase()));
+            }
+
+            let parsed = parse_with(alphabet, &alphabet.display(&symbols[..31]).to_string()).unwrap();
+            assert_eq!(&symbols[..31], parsed.symbols());
+            assert_eq!(None, parsed.erasures());
+        }
+    }
+
+    #[test]
+    fn crockford_aliases() {
+        let parsed = parse_with(&CROCKFORD, "OoIiLl01").unwrap();
+        assert_eq!(&[0, 0, 1, 1, 1, 1, 0, 1], parsed.symbols());
+        assert_eq!(None, parsed.erasures());
+        assert_eq!(None, CROCKFORD.from_char('U'));
+    }
+
+    #[test]
+    fn confusables() {
+        for c in "OoIiLl8Bb5Ss2Zz".chars() {
+            assert!(CROCKFORD.is_confusable(c));
+        }
+        for c in "0179AaXx-é".chars() {
+            assert!(!CROCKFORD.is_confusable(c));
+        }
+        assert!(BECH32.is_confusable('Z'));
+        assert!(!BECH32.is_confusable('q'));
+    }
+
+    #[test]
+    fn erasures() {
+        let parsed = parse_with(&RFC4648, "AB1C-8é").unwrap();
+        assert_eq!(&[0, 1, 0, 2, 0, 0, 0], parsed.symbols());
+        assert_eq!(Some(&[2, 4, 5, 6][..]), parsed.erasures());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn too_long() {
+        assert!(parse_with(&BECH32, &"q".repeat(31)).is_ok());
+        assert!(parse_with(&BECH32, &"q".repeat(32)).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        assert_eq!("ybndr", ZBASE32.display(&[0, 1, 2, 3, 4]).to_string());
+        assert_eq!("qpzry", BECH32.display(&[0, 1, 2, 3, 4]).to_string());
+        assert_eq!("ABCDE", RFC4648.display(&[0, 1, 2, 3, 4]).to_string());
+    }
+}
@@ -0,0 +1,341 @@
+//! Performance of the codes on a channel with random symbol errors and erasures.
+//!
+//! The codes of this crate are MDS codes: a code with `n` symbols, of which `k`
+//! are data symbols, has a minimum distance of `n - k + 1`. Their weight
+//! distributions have a closed form, which gives the probabilities of the
+//! different ways decoding can go wrong.
+//!
+//! All of the functions assume that each symbol is wrong independently with the
+//! same probability, and that a wrong symbol is equally likely to have any of
+//! the 31 wrong values. Decoding corrects `e` errors and `f` erasures as long
+//! as `2e + f <= n - k`.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::analysis;
+//!
+//! // 1 in 100 symbols are wrong, and at most 1 in a million messages of 20
+//! // data symbols may be lost
+//! let ecc = analysis::recommend_ecc(20, 0.01, 0.0, 1e-6).unwrap();
+//! assert_eq!(Some(10), ecc);
+//! assert!(analysis::block_error_rate(30, 20, 0.01).unwrap() <= 1e-6);
+//! assert!(analysis::block_error_rate(29, 20, 0.01).unwrap() > 1e-6);
+//! ```
+
+use crate::err::{invalid_combined_len, invalid_data_len, invalid_ecc, invalid_erase_pos, invalid_probability, UsageError};
+
+const Q: f64 = 32.0;
+
+/// Number of codewords of each weight
+///
+/// The counts quickly grow beyond what fits in an integer, so they are
+/// approximated by `f64`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WeightDistribution {
+    counts: [f64; crate::POLYNOMIAL_MAX_LENGTH + 1],
+    len: usize,
+}
+
+impl WeightDistribution {
+    /// Number of codewords of each weight from 0 to `n`
+    pub fn counts(&self) -> &[f64] {
+        &self.counts[..self.len]
+    }
+
+    /// Number of codewords with `weight` nonzero symbols
+    pub fn get(&self, weight: usize) -> f64 {
+        self.counts().get(weight).copied().unwrap_or(0.0)
+    }
+}
+
+/// Weight distribution of the code with `n` symbols, of which `k` are data symbols
+///
+/// Returns an error if `n` is greater than 31, if `k` is greater than `n` or
+/// if there would be 31 ECC symbols.
+pub fn weight_distribution(n: usize, k: usize) -> Result<WeightDistribution, UsageError> {
+    check_code(n, k)?;
+    let d = n - k + 1;
+    let mut distribution = WeightDistribution {
+        counts: [0.0; crate::POLYNOMIAL_MAX_LENGTH + 1],
+        len: n + 1,
+    };
+    distribution.counts[0] = 1.0;
+    for w in d..=n {
+        let mut sum = 0.0;
+        for j in 0..=w - d {
+            let sign = if j % 2 == 0 { 1.0 } else { -1.0 };
+            sum += sign * binomial(w, j) * (powi(Q, w - d + 1 - j) - 1.0);
+        }
+        distribution.counts[w] = binomial(n, w) * sum;
+    }
+    Ok(distribution)
+}
+
+/// Probability that symbol errors turn a codeword into a different codeword,
+/// so that they can't be detected, if each symbol is wrong with the
+/// probability `symbol_error_rate`
+pub fn undetected_error_probability(n: usize, k: usize, symbol_error_rate: f64) -> Result<f64, UsageError> {
+    check_probability(symbol_error_rate)?;
+    let distribution = weight_distribution(n, k)?;
+    let p = symbol_error_rate;
+    Ok((1..=n)
+        .map(|w| distribution.get(w) * powi(p / (Q - 1.0), w) * powi(1.0 - p, n - w))
+        .sum())
+}
+
+/// Probability that decoding a message with `errors` errors at random
+/// positions and `erasures` erasures returns the wrong codeword, rather than
+/// reporting `TooManyErrors`
+///
+/// This is 0 if the errors can be corrected. Returns an error if there are
+/// more errors and erasures than symbols.
+pub fn miscorrection_probability(n: usize, k: usize, errors: usize, erasures: usize) -> Result<f64, UsageError> {
+    check_code(n, k)?;
+    if errors + erasures > n {
+        return Err(invalid_erase_pos());
+    }
+    if erasures > n - k {
+        return Ok(0.0);
+    }
+
+    // Decoding with erasures is decoding the code without the erased
+    // symbols, which has the same number of data symbols.
+    let n = n - erasures;
+    let t = (n - k) / 2;
+    let distribution = weight_distribution(n, k)?;
+
+    // Number of words of weight `errors` within distance `t` of a codeword
+    // of weight `w`. Of the `w` nonzero symbols of the codeword, `i` are 0 in
+    // the word and `l` have a different nonzero value, and `j` of the other
+    // symbols are nonzero.
+    let mut decodable = 0.0;
+    for w in 1..=n {
+        let count = distribution.get(w);
+        if count == 0.0 {
+            continue;
+        }
+        let mut words = 0.0;
+        for j in 0..=(n - w).min(errors) {
+            let i = match (w + j).checked_sub(errors) {
+                Some(i) if i <= w => i,
+                _ => continue,
+            };
+            for l in 0..=w - i {
+                if i + j + l <= t {
+                    words += binomial(w, i) * binomial(w - i, l) * powi(Q - 2.0, l)
+                        * binomial(n - w, j) * powi(Q - 1.0, j);
+                }
+            }
+        }
+        decodable += count * words;
+    }
+    Ok(decodable / (binomial(n, errors) * powi(Q - 1.0, errors)))
+}
+
+/// Probability that a message can't be decoded to the right codeword, if each
+/// symbol is wrong with the probability `symbol_error_rate`
+///
+/// This includes the messages that are miscorrected as well as those for
+/// which decoding fails.
+pub fn block_error_rate(n: usize, k: usize, symbol_error_rate: f64) -> Result<f64, UsageError> {
+    block_error_rate_with_erasures(n, k, symbol_error_rate, 0.0)
+}
+
+/// Probability that a message can't be decoded to the right codeword, if each
+/// symbol is wrong with the probability `error_rate` and erased with the
+/// probability `erasure_rate`
+///
+/// Returns an error if the probabilities add up to more than 1.
+pub fn block_error_rate_with_erasures(n: usize, k: usize, error_rate: f64, erasure_rate: f64) -> Result<f64, UsageError> {
+    check_code(n, k)?;
+    check_probability(error_rate)?;
+    check_probability(erasure_rate)?;
+    check_probability(error_rate + erasure_rate)?;
+
+    // Rounding could otherwise make this slightly negative
+    let correct_rate = (1.0 - error_rate - erasure_rate).max(0.0);
+    let mut rate = 0.0;
+    for e in 0..=n {
+        for f in 0..=n - e {
+            if 2 * e + f > n - k {
+                rate += binomial(n, e) * binomial(n - e, f)
+                    * powi(error_rate, e) * powi(erasure_rate, f) * powi(correct_rate, n - e - f);
+            }
+        }
+    }
+    Ok(rate)
+}
+
+/// Smallest number of ECC symbols for which the
+/// [`block_error_rate_with_erasures`] of messages with `data_len` data symbols
+/// is at most `target`, or `None` if no message of 31 symbols or fewer is
+/// good enough
+pub fn recommend_ecc(data_len: usize, error_rate: f64, erasure_rate: f64, target: f64) -> Result<Option<u8>, UsageError> {
+    if data_len > crate::POLYNOMIAL_MAX_LENGTH {
+        return Err(invalid_data_len());
+    }
+    check_probability(target)?;
+    for ecc in 0..=(crate::POLYNOMIAL_MAX_LENGTH - data_len).min(30) {
+        if block_error_rate_with_erasures(data_len + ecc, data_len, error_rate, erasure_rate)? <= target {
+            return Ok(Some(ecc as u8));
+        }
+    }
+    Ok(None)
+}
+
+fn check_code(n: usize, k: usize) -> Result<(), UsageError> {
+    if n > crate::POLYNOMIAL_MAX_LENGTH {
+        return Err(invalid_combined_len());
+    }
+    if k > n {
+        return Err(invalid_data_len());
+    }
+    if n - k >= crate::POLYNOMIAL_MAX_LENGTH {
+        return Err(invalid_ecc());
+    }
+    Ok(())
+}
+
+fn check_probability(p: f64) -> Result<(), UsageError> {
+    // Written so that NaN fails as well
+    if !(0.0..=1.0).contains(&p) {
+        return Err(invalid_probability());
+    }
+    Ok(())
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+// f64::powi isn't available without std
+fn powi(x: f64, n: usize) -> f64 {
+    (0..n).fold(1.0, |acc, _| acc * x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::Decoder;
+    use crate::decoder_impl::correct_err_count;
+    use crate::encode;
+
+    fn close(a: f64, b: f64) -> bool {
+        let diff = if a > b { a - b } else { b - a };
+        diff <= 1e-9 * if a > b { a } else { b }
+    }
+
+    #[test]
+    fn weights() {
+        // Count the weights of all of the codewords of small codes
+        let mut counts = [0.0; 6];
+        for a in 0..32 {
+            for b in 0..32 {
+                for c in 0..32 {
+                    let encoded = encode(&[a, b, c], 2).unwrap();
+                    counts[encoded.iter().filter(|&&x| x != 0).count()] += 1.0;
+                }
+            }
+        }
+        assert_eq!(&counts[..], weight_distribution(5, 3).unwrap().counts());
+
+        for n in 0..=31 {
+            for k in 1..=n {
+                let distribution = weight_distribution(n, k).unwrap();
+                let total: f64 = distribution.counts().iter().sum();
+                assert!(close(powi(Q, k), total));
+                if k < n {
+                    assert_eq!(0.0, distribution.get(n - k));
+                }
+                assert!(close(binomial(n, n - k + 1) * (Q - 1.0), distribution.get(n - k + 1)));
+            }
+        }
+
+        assert!(weight_distribution(32, 3).is_err());
+        assert!(weight_distribution(5, 6).is_err());
+        assert!(weight_distribution(31, 0).is_err());
+    }
+
+    #[test]
+    fn miscorrection() {
+        // Decode every pattern of 2 errors in the zero codeword of a code
+        // that corrects 1 error.
+        let decoder = Decoder::new(2);
+        let mut total = 0;
+        let mut miscorrected = 0;
+        for p0 in 0..4 {
+            for p1 in p0 + 1..4 {
+                for v0 in 1..32 {
+                    for v1 in 1..32 {
+                        let mut msg = [0; 4];
+                        msg[p0] = v0;
+                        msg[p1] = v1;
+                        total += 1;
+                        if decoder.correct(&msg, None).is_ok() {
+                            miscorrected += 1;
+                        }
+                    }
+                }
+            }
+        }
+        let expected = miscorrected as f64 / total as f64;
+        assert!(close(expected, miscorrection_probability(4, 2, 2, 0).unwrap()));
+
+        // An erasure at position 0 leaves 1 error to miscorrect in a code of 3
+        // symbols that can't correct any.
+        let mut miscorrected = 0;
+        for p in 1..4 {
+            for v in 1..32 {
+                let mut msg = [0; 4];
+                msg[p] = v;
+                if correct_err_count(&msg, 2, Some(&[0])).is_ok() {
+                    miscorrected += 1;
+                }
+            }
+        }
+        assert_eq!(0, miscorrected);
+        assert_eq!(0.0, miscorrection_probability(4, 2, 1, 1).unwrap());
+
+        assert_eq!(0.0, miscorrection_probability(31, 15, 8, 0).unwrap());
+        assert!(miscorrection_probability(31, 15, 9, 0).unwrap() > 0.0);
+        assert_eq!(0.0, miscorrection_probability(31, 15, 0, 17).unwrap());
+        assert!(miscorrection_probability(31, 15, 20, 12).is_err());
+    }
+
+    #[test]
+    fn rates() {
+        assert_eq!(0.0, block_error_rate(20, 10, 0.0).unwrap());
+        assert!(close(1.0, block_error_rate(20, 10, 1.0).unwrap()));
+        assert!(close(1.0 - powi(0.99, 10), block_error_rate(10, 10, 0.01).unwrap()));
+
+        // Erasures alone only cause failures beyond n - k
+        let rate = block_error_rate_with_erasures(3, 1, 0.0, 0.5).unwrap();
+        assert!(close(0.125, rate));
+
+        // More ECC symbols always help
+        let mut previous = 1.0;
+        for ecc in 0..20 {
+            let rate = block_error_rate_with_erasures(10 + ecc, 10, 0.05, 0.02).unwrap();
+            assert!(rate < previous);
+            previous = rate;
+        }
+
+        assert_eq!(0.0, undetected_error_probability(10, 5, 0.0).unwrap());
+        let p_ud = undetected_error_probability(10, 5, 0.1).unwrap();
+        assert!(p_ud > 0.0 && p_ud < 1.0 / powi(Q, 5));
+
+        assert!(block_error_rate(10, 5, 1.5).is_err());
+        assert!(block_error_rate(10, 5, f64::NAN).is_err());
+        assert!(block_error_rate_with_erasures(10, 5, 0.6, 0.6).is_err());
+    }
+
+    #[test]
+    fn recommend() {
+        assert_eq!(Some(0), recommend_ecc(10, 0.0, 0.0, 0.0).unwrap());
+        let ecc = recommend_ecc(15, 0.02, 0.01, 1e-6).unwrap().unwrap();
+        assert!(block_error_rate_with_erasures(15 + ecc as usize, 15, 0.02, 0.01).unwrap() <= 1e-6);
+        assert!(block_error_rate_with_erasures(14 + ecc as usize, 15, 0.02, 0.01).unwrap() > 1e-6);
+        assert_eq!(None, recommend_ecc(30, 0.1, 0.0, 1e-9).unwrap());
+        assert!(recommend_ecc(32, 0.1, 0.0, 1e-9).is_err());
+    }
+}
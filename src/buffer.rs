@@ -1,3 +1,4 @@
+use crate::alphabet::{Alphabet, Display};
 use crate::gf::poly::Polynom;
 use core::ops::{Deref, DerefMut};
 
@@ -12,8 +13,8 @@ impl Buffer {
     /// Create buffer from internal polynom
     pub(crate) fn from_polynom(poly: Polynom, data_len: usize) -> Self {
         Buffer {
-            poly: poly,
-            data_len: data_len,
+            poly,
+            data_len,
         }
     }
 
@@ -21,7 +22,7 @@ impl Buffer {
     pub(crate) fn from_slice(slice: &[u8], data_len: usize) -> Self {
         Buffer {
             poly: Polynom::from(slice),
-            data_len: data_len,
+            data_len,
         }
     }
 
@@ -34,6 +35,27 @@ impl Buffer {
     pub fn ecc(&self) -> &[u8] {
         &self[self.data_len..]
     }
+
+    /// Returns a value that formats the whole block using `alphabet`
+    pub fn display_with<'a>(&'a self, alphabet: &'a Alphabet) -> Display<'a> {
+        alphabet.display(self)
+    }
+
+    /// Formats the whole block using `alphabet`
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encode;
+    /// use reed_solomon_32::alphabet::RFC4648;
+    ///
+    /// let encoded = encode(&[7, 4, 11, 11, 14], 6).unwrap();
+    ///
+    /// assert_eq!("HELLONAQSEG", encoded.to_string_with(&RFC4648));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_string_with(&self, alphabet: &Alphabet) -> String {
+        self.display_with(alphabet).to_string()
+    }
 }
 
 impl Deref for Buffer {
@@ -0,0 +1,150 @@
+use core::convert::TryFrom;
+use crate::buffer::Buffer;
+use crate::err::{invalid_codeword_len, invalid_data_len, invalid_symbol, UsageError};
+
+/// Fixed size encoded block of `N` symbols
+///
+/// Unlike [`Buffer`], a `Codeword` only stores the symbols that are part of the
+/// block, so two codewords with the same contents always compare and hash equal.
+/// This makes it suitable for use as a key in hash maps or as a field of other
+/// structures.
+///
+/// # Example
+/// ```rust
+/// use core::convert::TryFrom;
+/// use reed_solomon_32::{encode, Codeword};
+///
+/// let encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+/// let codeword = Codeword::<8>::try_from(encoded).unwrap();
+///
+/// assert_eq!(&[1, 2, 3, 4], codeword.data());
+/// assert_eq!(encoded.ecc(), codeword.ecc());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Codeword<const N: usize> {
+    symbols: [u8; N],
+    data_len: usize,
+}
+
+impl<const N: usize> Codeword<N> {
+    /// Create codeword from `symbols`, the first `data_len` of which are data
+    /// and the remaining ones are error correction code.
+    pub fn new(symbols: [u8; N], data_len: usize) -> Result<Self, UsageError> {
+        if N > crate::POLYNOMIAL_MAX_LENGTH {
+            return Err(invalid_data_len());
+        }
+        if data_len > N {
+            return Err(invalid_data_len());
+        }
+        if symbols.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        Ok(Codeword { symbols, data_len })
+    }
+
+    /// Slice with data of encoded block
+    pub fn data(&self) -> &[u8] {
+        &self.symbols[..self.data_len]
+    }
+
+    /// Slice with error correction code of encoded block
+    pub fn ecc(&self) -> &[u8] {
+        &self.symbols[self.data_len..]
+    }
+
+    /// Slice with the whole encoded block
+    pub fn as_slice(&self) -> &[u8] {
+        &self.symbols
+    }
+
+    /// Converts codeword into an array with the whole encoded block
+    pub fn into_array(self) -> [u8; N] {
+        self.symbols
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for Codeword<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.symbols
+    }
+}
+
+impl<const N: usize> TryFrom<&Buffer> for Codeword<N> {
+    type Error = UsageError;
+
+    fn try_from(buffer: &Buffer) -> Result<Self, Self::Error> {
+        if buffer.len() != N {
+            return Err(invalid_codeword_len());
+        }
+        let mut symbols = [0; N];
+        symbols.copy_from_slice(buffer);
+        Ok(Codeword { symbols, data_len: buffer.data().len() })
+    }
+}
+
+impl<const N: usize> TryFrom<Buffer> for Codeword<N> {
+    type Error = UsageError;
+
+    fn try_from(buffer: Buffer) -> Result<Self, Self::Error> {
+        Codeword::try_from(&buffer)
+    }
+}
+
+impl<const N: usize> From<Codeword<N>> for Buffer {
+    fn from(codeword: Codeword<N>) -> Buffer {
+        Buffer::from_slice(&codeword.symbols, codeword.data_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+    use super::Codeword;
+    use crate::{encode, Buffer};
+
+    #[test]
+    fn from_buffer() {
+        let encoded = encode(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], 8).unwrap();
+        let codeword = Codeword::<18>::try_from(encoded).unwrap();
+
+        assert_eq!(encoded.data(), codeword.data());
+        assert_eq!(encoded.ecc(), codeword.ecc());
+        assert_eq!(&encoded[..], codeword.as_slice());
+
+        let buffer = Buffer::from(codeword);
+        assert_eq!(encoded.data(), buffer.data());
+        assert_eq!(encoded.ecc(), buffer.ecc());
+    }
+
+    #[test]
+    fn from_buffer_wrong_len() {
+        let encoded = encode(&[0, 1, 2, 3], 4).unwrap();
+        assert!(Codeword::<7>::try_from(encoded).is_err());
+        assert!(Codeword::<9>::try_from(encoded).is_err());
+    }
+
+    #[test]
+    fn new() {
+        assert!(Codeword::new([1, 2, 3], 4).is_err());
+        assert!(Codeword::new([1, 2, 32], 2).is_err());
+        assert!(Codeword::new([0; 32], 2).is_err());
+
+        let codeword = Codeword::new([1, 2, 3], 2).unwrap();
+        assert_eq!([1, 2, 3], codeword.into_array());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash() {
+        use std::collections::HashSet;
+
+        let a = Codeword::<8>::try_from(encode(&[1, 2, 3, 4], 4).unwrap()).unwrap();
+        let b = Codeword::<8>::try_from(encode(&[1, 2, 3, 4], 4).unwrap()).unwrap();
+        let c = Codeword::<8>::try_from(encode(&[1, 2, 3, 5], 4).unwrap()).unwrap();
+
+        let mut set = HashSet::new();
+        set.insert(a);
+        assert!(set.contains(&b));
+        assert!(!set.contains(&c));
+    }
+}
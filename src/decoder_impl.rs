@@ -1,8 +1,12 @@
 use crate::gf::poly_math::*;
 use crate::gf::poly::Polynom;
 use crate::buffer::Buffer;
-use crate::err::{CorrectionError, invalid_data_len, invalid_data_len_for_ecc, invalid_ecc, invalid_erase_pos, invalid_symbol, UsageError};
+use crate::err::{CorrectionError, LocateError, invalid_codeword_len, invalid_combined_len, invalid_data_len, invalid_data_len_for_ecc, invalid_ecc, invalid_erase_pos, invalid_known_pos, invalid_max_errors, invalid_symbol, UsageError};
 use crate::gf;
+use crate::encoder_impl::select_encoder;
+use crate::layout::Layout;
+use crate::matrix::Matrix;
+use crate::symbol::Symbol;
 
 /// [`Decoder`] for messages with 0 ECC symbols
 pub const DECODER_0: Decoder = Decoder::new(0);
@@ -101,32 +105,84 @@ pub const DECODER_30: Decoder = Decoder::new(30);
 #[derive(Debug, Copy, Clone)]
 pub struct Decoder {
     ecc_len: u8,
+    // Limit on twice the number of errors plus the number of erasures
+    max_weight: u8,
 }
 
-fn check_message(msg: &[u8], ecc_len: u8) -> Result<(), UsageError> {
+pub(crate) fn check_message(msg: &[u8], ecc_len: u8) -> Result<(), UsageError> {
+    check_message_len(msg.len(), ecc_len)?;
+    if msg.iter().any(|&x| x > 31) {
+        return Err(invalid_symbol());
+    }
+    Ok(())
+}
+
+fn check_message_len(msg_len: usize, ecc_len: u8) -> Result<(), UsageError> {
     // NOTE: ecc_len is checked elsewhere - its not possible to create a Decoder
     // with an invalid ecc_len.
-    if msg.len() > 31 {
+    if msg_len > 31 {
         return Err(invalid_data_len());
     }
-    if msg.len() < ecc_len as usize {
+    if msg_len < ecc_len as usize {
         return Err(invalid_data_len_for_ecc());
     }
-    if msg.iter().any(|&x| x > 31) {
-        return Err(invalid_symbol());
-    }
     Ok(())
 }
 
 impl Decoder {
-    const fn new(ecc_len: u8) -> Self {
+    pub(crate) const fn new(ecc_len: u8) -> Self {
         if ecc_len >= 31 {
             // TODO: Make this a regular assert!() or return a Result once panics in const
             //       functions are allowed: https://rust-lang.github.io/rfcs/2345-const-panic.html
-            #[allow(unconditional_panic)]
+            #[allow(unconditional_panic, clippy::no_effect, clippy::out_of_bounds_indexing)]
             ["Invalid ECC Value"][1000];
         }
-        Decoder { ecc_len }
+        Decoder {
+            ecc_len,
+            max_weight: ecc_len,
+        }
+    }
+
+    pub(crate) const fn ecc_len(&self) -> u8 {
+        self.ecc_len
+    }
+
+    /// Returns decoder that corrects at most `max_errors` errors, where an
+    /// erasure counts as half an error, and reports anything beyond that as
+    /// `TooManyErrors`.
+    ///
+    /// Close to the limit of what can be corrected, there is a chance that a
+    /// message with too many errors is miscorrected to a different codeword.
+    /// Lowering the limit trades correction for detection: any message with
+    /// at most `ecc - max_errors` errors, and no erasures, is guaranteed to be
+    /// either corrected or reported as `TooManyErrors`.
+    ///
+    /// Returns an error if `max_errors` is greater than half the number of ECC symbols.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encode;
+    /// use reed_solomon_32::decoder::DECODER_8;
+    ///
+    /// let decoder = DECODER_8.with_max_errors(2).unwrap();
+    ///
+    /// let mut encoded = encode(&[1, 2, 3, 4], 8).unwrap();
+    /// encoded[0] = 0;
+    /// encoded[1] = 0;
+    /// assert_eq!(&[1, 2, 3, 4], decoder.correct(&encoded, None).unwrap().data());
+    ///
+    /// // DECODER_8 would correct this, but it is beyond the limit
+    /// encoded[2] = 0;
+    /// assert!(decoder.correct(&encoded, None).is_err());
+    /// ```
+    pub fn with_max_errors(&self, max_errors: u8) -> Result<Decoder, UsageError> {
+        if max_errors as usize * 2 > self.ecc_len as usize {
+            return Err(invalid_max_errors());
+        }
+        Ok(Decoder {
+            ecc_len: self.ecc_len,
+            max_weight: max_errors * 2,
+        })
     }
 
     /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
@@ -159,15 +215,580 @@ impl Decoder {
                              erase_pos: Option<&[u8]>)
                              -> Result<(Buffer, usize), CorrectionError> {
         check_message(msg, self.ecc_len)?;
+        self.correct_err_count_unchecked(msg, erase_pos)
+    }
+
+    /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
+    /// Also includes the number of errors corrected.
+    ///
+    /// This is the same as [`correct_err_count`](Decoder::correct_err_count), except that
+    /// the symbols are already known to be valid and aren't checked again.
+    pub fn correct_err_count_symbols(&self,
+                                     msg: &[Symbol],
+                                     erase_pos: Option<&[u8]>)
+                                     -> Result<(Buffer, usize), CorrectionError> {
+        check_message_len(msg.len(), self.ecc_len)?;
+        self.correct_err_count_unchecked(Symbol::slice_as_bytes(msg), erase_pos)
+    }
+
+    /// Decodes block-encoded message using the syndromes that were computed
+    /// while it was being received and returns `Buffer` with corrected message
+    /// and ecc offset. Also includes the number of errors corrected.
+    ///
+    /// This avoids computing the syndromes a second time. `syndromes` must have
+    /// been computed by pushing every symbol of `msg` and nothing else.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encode;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let mut encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+    /// encoded[2] = 1;
+    ///
+    /// let mut syndromes = DECODER_4.syndrome_state();
+    /// for &x in encoded.iter() {
+    ///     syndromes.push(x).unwrap();
+    /// }
+    /// assert!(syndromes.is_corrupted());
+    ///
+    /// let corrected = DECODER_4.correct_err_count_with_syndromes(&encoded, &syndromes, None).unwrap();
+    /// assert_eq!(&[1, 2, 3, 4], corrected.0.data());
+    /// assert_eq!(1, corrected.1);
+    /// ```
+    pub fn correct_err_count_with_syndromes(&self,
+                                            msg: &[u8],
+                                            syndromes: &SyndromeState,
+                                            erase_pos: Option<&[u8]>)
+                                            -> Result<(Buffer, usize), CorrectionError> {
+        if syndromes.ecc_len != self.ecc_len {
+            return Err(invalid_ecc().into());
+        }
+        if syndromes.msg_len != msg.len() {
+            return Err(invalid_codeword_len().into());
+        }
+        check_message(msg, self.ecc_len)?;
+        self.check_erasures(msg.len(), erase_pos)?;
+
+        let mut msg = Buffer::from_slice(msg, msg.len() - self.ecc_len as usize);
+        let mut synd = Polynom::with_length(self.ecc_len as usize + 1);
+        synd[1..].copy_from_slice(syndromes.syndromes());
+
+        // Remove the contribution of the erased symbols, as if they had been
+        // 0 when the syndromes were computed.
+        let erase_pos = erase_pos.unwrap_or(&[]);
+        for e_pos in erase_pos {
+            let value = msg[*e_pos as usize];
+            if value != 0 {
+                let degree = (msg.len() - 1 - *e_pos as usize) as i32;
+                for i in 0..self.ecc_len as usize {
+                    synd[i + 1] ^= gf::mul(value, gf::pow(2, i as i32 * degree));
+                }
+                msg[*e_pos as usize] = 0;
+            }
+        }
+
+        self.correct_from_syndromes(msg, &synd, erase_pos)
+    }
+
+    /// Decodes block-encoded message where the symbols at `known_pos` are known
+    /// to be correct and returns `Buffer` with corrected message and ecc offset.
+    /// Also includes the number of errors corrected.
+    ///
+    /// The known symbols are never changed. If the closest codeword to the
+    /// message differs from it at one of the known positions, the message had
+    /// more errors than could be corrected and `TooManyErrors` is returned
+    /// instead of a wrong correction.
+    ///
+    /// The known symbols are only used for this check. The message is decoded
+    /// the same as by [`correct_err_count`](Decoder::correct_err_count), so
+    /// knowing symbols doesn't allow any more errors to be corrected.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encode;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let mut encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+    /// encoded[2] = 0;
+    ///
+    /// // The first symbol is known to be 1
+    /// let corrected = DECODER_4.correct_with_known(&encoded, &[0], None).unwrap();
+    /// assert_eq!(&[1, 2, 3, 4], corrected.0.data());
+    /// assert_eq!(1, corrected.1);
+    /// ```
+    pub fn correct_with_known(&self,
+                              msg: &[u8],
+                              known_pos: &[u8],
+                              erase_pos: Option<&[u8]>)
+                              -> Result<(Buffer, usize), CorrectionError> {
+        let erase_pos_contains = |pos| erase_pos.map_or(false, |erase_pos| erase_pos.contains(pos));
+        if known_pos.iter().any(|pos| *pos as usize >= msg.len() || erase_pos_contains(pos)) {
+            return Err(invalid_known_pos().into());
+        }
+
+        let (corrected, fixed) = self.correct_err_count(msg, erase_pos)?;
+        if known_pos.iter().any(|&pos| corrected[pos as usize] != msg[pos as usize]) {
+            return Err(CorrectionError::TooManyErrors);
+        }
+        Ok((corrected, fixed))
+    }
+
+    /// Decodes block-encoded message of a shortened code where the first data
+    /// symbols are `prefix`, which isn't part of `msg`. Returns `Buffer` with
+    /// the corrected message, including the prefix, and ecc offset. Also
+    /// includes the number of errors corrected.
+    ///
+    /// `erase_pos` are positions in `msg`. The same as with
+    /// [`correct_with_known`](Decoder::correct_with_known), `TooManyErrors` is
+    /// returned if the closest codeword doesn't start with `prefix`. The prefix
+    /// is only used for this check, so it doesn't allow any more errors to be
+    /// corrected in `msg`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encode;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// // The header [7, 7] is never sent
+    /// let encoded = encode(&[7, 7, 1, 2, 3, 4], 4).unwrap();
+    /// let mut sent = [0; 8];
+    /// sent.copy_from_slice(&encoded[2..]);
+    /// sent[1] = 0;
+    ///
+    /// let corrected = DECODER_4.correct_with_known_prefix(&sent, &[7, 7], None).unwrap();
+    /// assert_eq!(&[7, 7, 1, 2, 3, 4], corrected.0.data());
+    /// assert_eq!(1, corrected.1);
+    /// ```
+    pub fn correct_with_known_prefix(&self,
+                                     msg: &[u8],
+                                     prefix: &[u8],
+                                     erase_pos: Option<&[u8]>)
+                                     -> Result<(Buffer, usize), CorrectionError> {
+        let len = prefix.len() + msg.len();
+        if len > 31 {
+            return Err(invalid_data_len().into());
+        }
+        let mut full = Polynom::with_length(len);
+        full[..prefix.len()].copy_from_slice(prefix);
+        full[prefix.len()..].copy_from_slice(msg);
+
+        let mut full_erase_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        let erase_pos = match erase_pos {
+            Some(erase_pos) => {
+                if erase_pos.len() > self.ecc_len as usize {
+                    return Err(CorrectionError::TooManyErrors);
+                }
+                for (x, &pos) in full_erase_pos.iter_mut().zip(erase_pos) {
+                    if pos as usize >= msg.len() {
+                        return Err(invalid_erase_pos().into());
+                    }
+                    *x = pos + prefix.len() as u8;
+                }
+                Some(&full_erase_pos[..erase_pos.len()])
+            }
+            None => None,
+        };
+
+        let mut known_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        for (i, x) in known_pos[..prefix.len()].iter_mut().enumerate() {
+            *x = i as u8;
+        }
+
+        self.correct_with_known(&full, &known_pos[..prefix.len()], erase_pos)
+    }
+
+    /// Decodes block-encoded message where all of the errors are at the known
+    /// positions `erase_pos` and returns `Buffer` with corrected message and ecc offset.
+    ///
+    /// This skips searching for errors at unknown positions, which makes it faster
+    /// than [`correct`](Decoder::correct). Up to `ecc` distinct erasures can be
+    /// recovered. If the message has errors at other positions, `TooManyErrors`
+    /// is returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encode;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let mut encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+    /// encoded[0] = 0;
+    /// encoded[2] = 0;
+    /// encoded[5] = 0;
+    /// encoded[7] = 0;
+    ///
+    /// let recovered = DECODER_4.recover_erasures(&encoded, &[0, 2, 5, 7]).unwrap();
+    /// assert_eq!(&[1, 2, 3, 4], recovered.data());
+    /// ```
+    pub fn recover_erasures(&self, msg: &[u8], erase_pos: &[u8]) -> Result<Buffer, CorrectionError> {
+        check_message(msg, self.ecc_len)?;
+        self.check_erasures(msg.len(), Some(erase_pos))?;
+        if erase_pos.iter().enumerate().any(|(i, pos)| erase_pos[..i].contains(pos)) {
+            return Err(invalid_erase_pos().into());
+        }
+
+        let mut msg = Buffer::from_slice(msg, msg.len() - self.ecc_len as usize);
+        for e_pos in erase_pos {
+            msg[*e_pos as usize] = 0;
+        }
+
+        let synd = self.calc_syndromes(&msg);
+        if synd.iter().all(|x| *x == 0) {
+            return Ok(msg);
+        }
+
+        let (msg_out, _) = self.correct_errata(&msg, &synd, erase_pos);
+
+        // Check output message correctness
+        if self.is_corrupted_unchecked(&msg_out) {
+            Err(CorrectionError::TooManyErrors)
+        } else {
+            Ok(Buffer::from_polynom(msg_out, msg.len() - self.ecc_len as usize))
+        }
+    }
+
+    /// Finds the positions of the symbols of the message that are wrong, and
+    /// what they would need to be XORed with to correct them, without
+    /// correcting the message.
+    ///
+    /// Erasures that already have the right value aren't included. If the
+    /// message can't be corrected, the error includes an estimate of how many
+    /// errors it has.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::{encode, LocateError};
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let mut encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+    /// encoded[1] ^= 5;
+    /// encoded[6] ^= 9;
+    ///
+    /// let locations = DECODER_4.locate_errors(&encoded, None).unwrap();
+    /// assert_eq!(&[1, 6], locations.positions());
+    /// assert_eq!(&[5, 9], locations.magnitudes());
+    ///
+    /// encoded[0] ^= 1;
+    /// match DECODER_4.locate_errors(&encoded, None) {
+    ///     Err(LocateError::TooManyErrors { estimated_errors }) => assert_eq!(3, estimated_errors),
+    ///     _ => panic!("3 errors can't be located with 4 ECC symbols"),
+    /// }
+    /// ```
+    pub fn locate_errors(&self, msg: &[u8], erase_pos: Option<&[u8]>) -> Result<ErrorLocations, LocateError> {
+        check_message(msg, self.ecc_len)?;
+        let erase_pos = erase_pos.unwrap_or(&[]);
+        if erase_pos.iter().any(|&pos| pos as usize >= msg.len()) {
+            return Err(invalid_erase_pos().into());
+        }
+        if erase_pos.iter().enumerate().any(|(i, pos)| erase_pos[..i].contains(pos)) {
+            return Err(invalid_erase_pos().into());
+        }
+        if erase_pos.len() > self.max_weight as usize {
+            return Err(LocateError::TooManyErasures);
+        }
+
+        let mut locations = ErrorLocations {
+            positions: [0; crate::POLYNOMIAL_MAX_LENGTH],
+            magnitudes: [0; crate::POLYNOMIAL_MAX_LENGTH],
+            len: 0,
+        };
+
+        // The erasures aren't zeroed, so that their magnitudes are relative to
+        // the values in the message.
+        let synd = self.calc_syndromes(msg);
+        if synd.iter().all(|x| *x == 0) {
+            return Ok(locations);
+        }
+
+        // The degree of the locator is the number of errors if the message
+        // can be corrected, and only an estimate otherwise.
+        let fsynd = self.forney_syndromes(&synd, erase_pos, msg.len());
+        let err_loc = self.berlekamp_massey(&fsynd, None, erase_pos.len());
+        let estimated_errors = err_loc.len() - 1;
+
+        let mut err_pos = self.check_error_locator(&err_loc, None, erase_pos.len())
+            .and_then(|()| self.find_error_positions(&err_loc, erase_pos, msg.len()))
+            .map_err(|_| LocateError::TooManyErrors { estimated_errors })?;
+        for x in erase_pos.iter() {
+            err_pos.push(*x);
+        }
+        let magnitudes = self.errata_magnitudes(msg.len(), &synd, &err_pos);
+
+        // The errata are only right if they account for all of the syndromes
+        for i in 0..self.ecc_len as usize {
+            let mut s = synd[i + 1];
+            for (&pos, &magnitude) in err_pos.iter().zip(magnitudes.iter()) {
+                let degree = (msg.len() - 1 - pos as usize) as i32;
+                s ^= gf::mul(magnitude, gf::pow(2, i as i32 * degree));
+            }
+            if s != 0 {
+                return Err(LocateError::TooManyErrors { estimated_errors });
+            }
+        }
+
+        for (&pos, &magnitude) in err_pos.iter().zip(magnitudes.iter()) {
+            if magnitude != 0 {
+                locations.positions[locations.len] = pos;
+                locations.magnitudes[locations.len] = magnitude;
+                locations.len += 1;
+            }
+        }
+
+        locations.sort();
+        Ok(locations)
+    }
+
+    /// Corrects a message whose data and ECC symbols are stored in separate
+    /// slices, in place. Returns the number of errors corrected.
+    ///
+    /// `erase_pos` are positions in the message made up of `data` followed by
+    /// `ecc`. If the message can't be corrected, `data` and `ecc` are left unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let mut data = [1, 2, 3, 4];
+    /// let mut ecc: [u8; 4] = ENCODER_4.parity(&data).unwrap();
+    ///
+    /// // Corrupt data and ecc
+    /// data[1] = 0;
+    /// ecc[2] ^= 1;
+    ///
+    /// assert!(DECODER_4.is_corrupted_detached(&data, &ecc).unwrap());
+    ///
+    /// let fixed = DECODER_4.correct_detached(&mut data, &mut ecc, None).unwrap();
+    /// assert_eq!(2, fixed);
+    /// assert_eq!([1, 2, 3, 4], data);
+    /// assert_eq!(ENCODER_4.parity::<4>(&data).unwrap(), ecc);
+    /// ```
+    pub fn correct_detached(&self,
+                            data: &mut [u8],
+                            ecc: &mut [u8],
+                            erase_pos: Option<&[u8]>)
+                            -> Result<usize, CorrectionError> {
+        let syndromes = self.detached_syndromes(data, ecc)?;
+        let msg_len = data.len() + ecc.len();
+        self.check_erasures(msg_len, erase_pos)?;
+
+        // No errors
+        if !syndromes.is_corrupted() {
+            return Ok(0);
+        }
+
+        let mut synd = Polynom::with_length(self.ecc_len as usize + 1);
+        synd[1..].copy_from_slice(syndromes.syndromes());
+
+        // The erased symbols don't need to be zeroed, their magnitudes are
+        // computed relative to whatever value they currently have.
+        let err_pos = self.find_errata(&synd, erase_pos.unwrap_or(&[]), msg_len)?;
+        let magnitudes = self.errata_magnitudes(msg_len, &synd, &err_pos);
+
+        let apply = |data: &mut [u8], ecc: &mut [u8]| {
+            for (pos, magnitude) in err_pos.iter().zip(magnitudes.iter()) {
+                let pos = *pos as usize;
+                if pos < data.len() {
+                    data[pos] ^= magnitude;
+                } else {
+                    ecc[pos - data.len()] ^= magnitude;
+                }
+            }
+        };
+
+        apply(data, ecc);
+
+        // Check output message correctness
+        if self.detached_syndromes(data, ecc)?.is_corrupted() {
+            apply(data, ecc);
+            Err(CorrectionError::TooManyErrors)
+        } else {
+            Ok(err_pos.len())
+        }
+    }
+
+    /// Checks if a message whose data and ECC symbols are stored in separate
+    /// slices is corrupted.
+    pub fn is_corrupted_detached(&self, data: &[u8], ecc: &[u8]) -> Result<bool, UsageError> {
+        Ok(self.detached_syndromes(data, ecc)?.is_corrupted())
+    }
+
+    fn detached_syndromes(&self, data: &[u8], ecc: &[u8]) -> Result<SyndromeState, UsageError> {
+        if ecc.len() != self.ecc_len as usize {
+            return Err(invalid_codeword_len());
+        }
+        check_message_len(data.len() + ecc.len(), self.ecc_len)?;
+
+        let mut syndromes = self.syndrome_state();
+        for &x in data.iter().chain(ecc.iter()) {
+            syndromes.push(x)?;
+        }
+        Ok(syndromes)
+    }
+
+    /// Corrects a message whose data and ECC symbols are placed according to
+    /// `layout`, in place. Returns the number of errors corrected.
+    ///
+    /// `erase_pos` are physical positions in `msg`. If the message can't be
+    /// corrected, `msg` is left unchanged. Use
+    /// [`locate_errors_with_layout`](Decoder::locate_errors_with_layout) to find
+    /// the physical positions of the errors.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::Layout;
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let layout = Layout::prefix();
+    /// let mut msg = [0; 8];
+    /// ENCODER_4.encode_with_layout(&[1, 2, 3, 4], &layout, &mut msg).unwrap();
+    ///
+    /// // Corrupt an ECC symbol and a data symbol, which is known to be wrong
+    /// msg[0] ^= 1;
+    /// msg[6] = 0;
+    ///
+    /// let fixed = DECODER_4.correct_with_layout(&mut msg, &layout, Some(&[6])).unwrap();
+    /// assert_eq!(2, fixed);
+    /// assert_eq!(&[1, 2, 3, 4], &msg[4..]);
+    /// ```
+    pub fn correct_with_layout(&self,
+                               msg: &mut [u8],
+                               layout: &Layout,
+                               erase_pos: Option<&[u8]>)
+                               -> Result<usize, CorrectionError> {
+        self.check_erasures(msg.len(), erase_pos)?;
+        let mut logical_erase_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        let logical = self.logical_message(msg, layout, erase_pos, &mut logical_erase_pos)?;
+        let erase_pos = erase_pos.map(|erase_pos| &logical_erase_pos[..erase_pos.len()]);
+
+        let data_len = msg.len() - self.ecc_len as usize;
+        let (corrected, fixed) = self.correct_err_count_unchecked(&logical, erase_pos)?;
+        for (i, &x) in corrected.iter().enumerate() {
+            msg[layout.physical_index_unchecked(i, data_len, self.ecc_len as usize)] = x;
+        }
+        Ok(fixed)
+    }
+
+    /// Finds the errors of a message whose data and ECC symbols are placed
+    /// according to `layout`, without correcting it.
+    ///
+    /// Both `erase_pos` and the positions of the returned errors are physical
+    /// positions in `msg`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::Layout;
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// let layout = Layout::prefix();
+    /// let mut msg = [0; 8];
+    /// ENCODER_4.encode_with_layout(&[1, 2, 3, 4], &layout, &mut msg).unwrap();
+    /// msg[0] ^= 1;
+    /// msg[6] ^= 2;
+    ///
+    /// let locations = DECODER_4.locate_errors_with_layout(&msg, &layout, None).unwrap();
+    /// assert_eq!(&[0, 6], locations.positions());
+    /// assert_eq!(&[1, 2], locations.magnitudes());
+    /// ```
+    pub fn locate_errors_with_layout(&self,
+                                     msg: &[u8],
+                                     layout: &Layout,
+                                     erase_pos: Option<&[u8]>)
+                                     -> Result<ErrorLocations, LocateError> {
+        let mut logical_erase_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        let logical = self.logical_message(msg, layout, erase_pos, &mut logical_erase_pos)?;
+        let erase_pos = erase_pos.map(|erase_pos| &logical_erase_pos[..erase_pos.len()]);
+
+        let data_len = msg.len() - self.ecc_len as usize;
+        let mut locations = self.locate_errors(&logical, erase_pos)?;
+        for pos in locations.positions[..locations.len].iter_mut() {
+            *pos = layout.physical_index_unchecked(*pos as usize, data_len, self.ecc_len as usize) as u8;
+        }
+        locations.sort();
+        Ok(locations)
+    }
+
+    /// Checks `msg` and the positions in `erase_pos`, and reorders `msg` from
+    /// the physical positions of `layout` into the logical message. The
+    /// logical positions of the erasures are written to `logical_erase_pos`.
+    ///
+    /// The number of erasures isn't checked.
+    fn logical_message(&self,
+                       msg: &[u8],
+                       layout: &Layout,
+                       erase_pos: Option<&[u8]>,
+                       logical_erase_pos: &mut [u8; crate::POLYNOMIAL_MAX_LENGTH])
+                       -> Result<Polynom, UsageError> {
+        check_message(msg, self.ecc_len)?;
+        if erase_pos.unwrap_or(&[]).iter().any(|&pos| pos as usize >= msg.len()) {
+            return Err(invalid_erase_pos());
+        }
+        let ecc_len = self.ecc_len as usize;
+        let data_len = msg.len() - ecc_len;
+        layout.check(data_len, ecc_len)?;
+
+        let mut logical = Polynom::with_length(msg.len());
+        for (i, x) in logical.iter_mut().enumerate() {
+            *x = msg[layout.physical_index_unchecked(i, data_len, ecc_len)];
+        }
+        for (x, pos) in logical_erase_pos.iter_mut().zip(erase_pos.unwrap_or(&[])) {
+            *x = layout.logical_index_unchecked(*pos as usize, data_len, ecc_len) as u8;
+        }
+        Ok(logical)
+    }
+
+    /// Parity check matrix in systematic form `[Pᵀ | I]` for messages with
+    /// `msg_len` symbols
+    ///
+    /// This is the [`dual`](Matrix::dual) of the
+    /// [`generator_matrix`](crate::encoder::Encoder::generator_matrix) of the
+    /// encoder with the same number of ECC symbols. Multiplying it by a valid
+    /// message gives all zeros.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::decoder::DECODER_4;
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    ///
+    /// let g = ENCODER_4.generator_matrix(3).unwrap();
+    /// let h = DECODER_4.parity_check_matrix(7).unwrap();
+    /// assert!(g.mul(&h.transpose()).unwrap().is_zero());
+    /// ```
+    pub fn parity_check_matrix(&self, msg_len: usize) -> Result<Matrix, UsageError> {
+        check_message_len(msg_len, self.ecc_len)?;
+        let encoder = select_encoder(self.ecc_len)?;
+        encoder.generator_matrix(msg_len - self.ecc_len as usize)?.dual()
+    }
+
+    /// Creates [`SyndromeState`] for computing the syndromes of a message
+    /// one symbol at a time, while it is being received.
+    pub fn syndrome_state(&self) -> SyndromeState {
+        SyndromeState {
+            ecc_len: self.ecc_len,
+            synd: [0; crate::POLYNOMIAL_MAX_LENGTH - 1],
+            msg_len: 0,
+        }
+    }
 
+    pub(crate) fn check_erasures(&self, msg_len: usize, erase_pos: Option<&[u8]>) -> Result<(), CorrectionError> {
         if let Some(x) = erase_pos {
-            if x.len() > self.ecc_len as usize {
+            if x.len() > self.max_weight as usize {
                 return Err(CorrectionError::TooManyErrors);
             }
-            if x.iter().any(|&err_pos| err_pos as usize > msg.len()) {
+            if x.iter().any(|&err_pos| err_pos as usize >= msg_len) {
                 return Err(invalid_erase_pos().into());
             }
         }
+        Ok(())
+    }
+
+    fn correct_err_count_unchecked(&self,
+                                   msg: &[u8],
+                                   erase_pos: Option<&[u8]>)
+                                   -> Result<(Buffer, usize), CorrectionError> {
+        self.check_erasures(msg.len(), erase_pos)?;
 
        let mut msg = Buffer::from_slice(msg, msg.len() - self.ecc_len as usize);
 
@@ -181,25 +802,24 @@ impl Decoder {
         };
 
         let synd = self.calc_syndromes(&msg);
+        self.correct_from_syndromes(msg, &synd, erase_pos)
+    }
 
+    fn correct_from_syndromes(&self,
+                              msg: Buffer,
+                              synd: &[u8],
+                              erase_pos: &[u8])
+                              -> Result<(Buffer, usize), CorrectionError> {
         // No errors
         if synd.iter().all(|x| *x == 0) {
             return Ok((msg,0));
         }
 
-        let fsynd = self.forney_syndromes(&synd, erase_pos, msg.len());
-        let err_loc = self.find_error_locator(&fsynd, None, erase_pos.len())?;
-        let mut err_pos = self.find_errors(&err_loc.reverse(), msg.len())?;
-
-        // Append erase_pos to err_pos
-        for x in erase_pos.iter() {
-            err_pos.push(*x);
-        }
-
-        let (msg_out, fixed) = self.correct_errata(&msg, &synd, &err_pos);
+        let err_pos = self.find_errata(synd, erase_pos, msg.len())?;
+        let (msg_out, fixed) = self.correct_errata(&msg, synd, &err_pos);
 
         // Check output message correctness
-        if self.is_corrupted(&msg_out)? {
+        if self.is_corrupted_unchecked(&msg_out) {
             Err(CorrectionError::TooManyErrors)
         } else {
             Ok((Buffer::from_polynom(msg_out, msg.len() - self.ecc_len as usize), fixed))
@@ -236,6 +856,36 @@ impl Decoder {
         self.correct_err_count(msg, erase_pos).map(|(r,_)| r)
      }
 
+    /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
+    ///
+    /// This is the same as [`correct`](Decoder::correct), except that
+    /// the symbols are already known to be valid and aren't checked again.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::{encode, Symbol};
+    /// use reed_solomon_32::decoder::DECODER_4;
+    ///
+    /// // Encode message
+    /// let mut encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+    ///
+    /// // Corrupt message
+    /// encoded[2] = 1;
+    ///
+    /// // Decode and correct message,
+    /// let symbols = Symbol::slice_from_bytes(&encoded).unwrap();
+    /// let corrected = DECODER_4.correct_symbols(symbols, None).unwrap();
+    ///
+    /// // Check results
+    /// assert_eq!(&[1, 2, 3, 4], corrected.data())
+    /// ```
+    pub fn correct_symbols(&self,
+                           msg: &[Symbol],
+                           erase_pos: Option<&[u8]>)
+                           -> Result<Buffer, CorrectionError> {
+        self.correct_err_count_symbols(msg, erase_pos).map(|(r, _)| r)
+    }
+
     /// Performs fast corruption check.
     ///
     /// # Example
@@ -256,10 +906,23 @@ impl Decoder {
     /// ```
     pub fn is_corrupted(&self, msg: &[u8]) -> Result<bool, UsageError> {
         check_message(msg, self.ecc_len)?;
-        Ok((0..self.ecc_len).any(|x| msg.eval(gf::pow(2, x as i32)) != 0))
+        Ok(self.is_corrupted_unchecked(msg))
     }
 
-    fn calc_syndromes(&self, msg: &[u8]) -> Polynom {
+    /// Performs fast corruption check.
+    ///
+    /// This is the same as [`is_corrupted`](Decoder::is_corrupted), except that
+    /// the symbols are already known to be valid and aren't checked again.
+    pub fn is_corrupted_symbols(&self, msg: &[Symbol]) -> Result<bool, UsageError> {
+        check_message_len(msg.len(), self.ecc_len)?;
+        Ok(self.is_corrupted_unchecked(Symbol::slice_as_bytes(msg)))
+    }
+
+    pub(crate) fn is_corrupted_unchecked(&self, msg: &[u8]) -> bool {
+        (0..self.ecc_len).any(|x| msg.eval(gf::pow(2, x as i32)) != 0)
+    }
+
+    pub(crate) fn calc_syndromes(&self, msg: &[u8]) -> Polynom {
         // index 0 is a pad for mathematical precision
         let mut synd = Polynom::with_length(self.ecc_len as usize + 1);
         for i in 0..self.ecc_len as usize {
@@ -269,7 +932,7 @@ impl Decoder {
         synd
     }
 
-    fn find_errata_locator(&self, e_pos: &[u8]) -> Polynom {
+    pub(crate) fn find_errata_locator(&self, e_pos: &[u8]) -> Polynom {
         let mut e_loc = polynom![1];
 
         let add_lhs = [1];
@@ -282,7 +945,7 @@ impl Decoder {
         e_loc
     }
 
-    fn find_error_evaluator(&self, synd: &[u8], err_loc: &[u8], syms: usize) -> Polynom {
+    pub(crate) fn find_error_evaluator(&self, synd: &[u8], err_loc: &[u8], syms: usize) -> Polynom {
         let mut divisor = Polynom::with_length(syms + 2);
         divisor[0] = 1;
 
@@ -290,13 +953,53 @@ impl Decoder {
         remainder
     }
 
+    /// Finds the positions of the errata of a message with `msg_len` symbols
+    /// and the syndromes `synd`, along with their magnitudes.
+    pub(crate) fn errata(&self,
+                         synd: &[u8],
+                         erase_pos: &[u8],
+                         msg_len: usize)
+                         -> Result<(Polynom, Polynom), CorrectionError> {
+        let err_pos = self.find_errata(synd, erase_pos, msg_len)?;
+        let magnitudes = self.errata_magnitudes(msg_len, synd, &err_pos);
+        Ok((err_pos, magnitudes))
+    }
+
+    /// Finds the positions of the errors and appends the erasure positions to them.
+    fn find_errata(&self, synd: &[u8], erase_pos: &[u8], msg_len: usize) -> Result<Polynom, CorrectionError> {
+        let fsynd = self.forney_syndromes(synd, erase_pos, msg_len);
+        let err_loc = self.find_error_locator(&fsynd, None, erase_pos.len())?;
+        let mut err_pos = self.find_error_positions(&err_loc, erase_pos, msg_len)?;
+
+        // Append erase_pos to err_pos
+        for x in erase_pos.iter() {
+            err_pos.push(*x);
+        }
+
+        Ok(err_pos)
+    }
+
+    /// Corrects the input message using the magnitudes computed by [`errata_magnitudes`](Decoder::errata_magnitudes).
+    fn correct_errata(&self, msg: &[u8], synd: &[u8], err_pos: &[u8]) -> (Polynom, usize) {
+        let magnitudes = self.errata_magnitudes(msg.len(), synd, err_pos);
+
+        let mut e = Polynom::with_length(msg.len());
+        for (pos, magnitude) in err_pos.iter().zip(magnitudes.iter()) {
+            e[*pos as usize] = *magnitude;
+        }
+
+        (msg.add(&e), err_pos.len())
+    }
+
     /// Forney algorithm, computes the values (error magnitude) to correct the input message.
+    ///
+    /// The magnitudes are returned in the same order as `err_pos`.
     #[allow(non_snake_case)]
-    fn correct_errata(&self, msg: &[u8], synd: &[u8], err_pos: &[u8]) -> (Polynom, usize) {
+    pub(crate) fn errata_magnitudes(&self, msg_len: usize, synd: &[u8], err_pos: &[u8]) -> Polynom {
         // convert the positions to coefficients degrees
         let mut coef_pos = Polynom::with_length(err_pos.len());
         for (i, x) in err_pos.iter().enumerate() {
-            coef_pos[i] = msg.len() as u8 - 1 - x;
+            coef_pos[i] = msg_len as u8 - 1 - x;
         }
 
         let err_loc = self.find_errata_locator(&coef_pos);
@@ -311,8 +1014,7 @@ impl Decoder {
             X.push(gf::pow(2, -l))
         }
 
-        let mut E = Polynom::with_length(msg.len());
-        let mut fixed = 0;
+        let mut magnitudes = Polynom::with_length(err_pos.len());
 
         let err_eval_rev = err_eval.reverse();
         for (i, Xi) in X.iter().enumerate() {
@@ -333,22 +1035,53 @@ impl Decoder {
             let y = err_eval_rev.eval(Xi_inv);
             let y = gf::mul(gf::pow(*Xi, 1), y);
 
-            let magnitude = gf::div(y, err_loc_prime);
-
-            let E_index = err_pos[i] as usize;
-            E[E_index] = magnitude;
-            fixed += 1;
+            magnitudes[i] = gf::div(y, err_loc_prime);
         }
 
-        (msg.add(&E), fixed)
+        magnitudes
     }
 
-    #[allow(non_snake_case)]
-    fn find_error_locator(&self,
+    pub(crate) fn find_error_locator(&self,
                           synd: &[u8],
                           erase_loc: Option<&[u8]>,
                           erase_count: usize)
                           -> Result<Polynom, CorrectionError> {
+        let err_loc = self.berlekamp_massey(synd, erase_loc, erase_count);
+        self.check_error_locator(&err_loc, erase_loc, erase_count)?;
+        Ok(err_loc)
+    }
+
+    /// Checks that the errors found by the locator and the erasures don't use
+    /// more ECC symbols than the decoder may use.
+    fn check_error_locator(&self,
+                           err_loc: &[u8],
+                           erase_loc: Option<&[u8]>,
+                           erase_count: usize)
+                           -> Result<(), CorrectionError> {
+        // Without erase_loc, the locator was computed from the Forney syndromes
+        // and only covers the errors, not the erasures.
+        let errs = err_loc.len() - 1;
+        let errs = if erase_loc.is_none() {
+            errs * 2 + erase_count
+        } else if erase_count > errs {
+            erase_count
+        } else {
+            (errs - erase_count) * 2 + erase_count
+        };
+
+        if errs > self.max_weight as usize {
+            Err(CorrectionError::TooManyErrors)
+        } else {
+            Ok(())
+        }
+    }
+
+    #[allow(non_snake_case)]
+    fn berlekamp_massey(&self,
+                        synd: &[u8],
+                        erase_loc: Option<&[u8]>,
+                        erase_count: usize)
+                        -> Polynom {
         let (mut err_loc, mut old_loc) = if let Some(erase_loc) = erase_loc {
             (Polynom::from(erase_loc), Polynom::from(erase_loc))
         } else {
@@ -388,20 +1121,26 @@ impl Decoder {
         }
 
         let shift = err_loc.iter().take_while(|&&v| v == 0).count();
-        let err_loc = Polynom::from(&err_loc[shift..]);
-
-        let errs = err_loc.len() - 1;
-        let errs = if erase_count > errs {
-            erase_count
-        } else {
-            (errs - erase_count) * 2 + erase_count
-        };
+        Polynom::from(&err_loc[shift..])
+    }
 
-        if errs > self.ecc_len as usize {
-            Err(CorrectionError::TooManyErrors)
-        } else {
-            Ok(err_loc)
+    /// Finds the positions of the errors from the locator computed from the
+    /// Forney syndromes, which has its highest degree coefficient first.
+    pub(crate) fn find_error_positions(&self,
+                                       err_loc: &[u8],
+                                       erase_pos: &[u8],
+                                       msg_len: usize)
+                                       -> Result<Polynom, CorrectionError> {
+        let err_pos = self.find_errors(&Polynom::from(err_loc).reverse(), msg_len)?;
+
+        // An error found at one of the erasures means that the message can't
+        // be corrected, and would otherwise cause a division by 0 in the
+        // Forney algorithm.
+        if err_pos.iter().any(|x| erase_pos.contains(x)) {
+            return Err(CorrectionError::TooManyErrors);
         }
+
+        Ok(err_pos)
     }
 
     fn find_errors(&self, err_loc: &[u8], msg_len: usize) -> Result<Polynom, CorrectionError> {
@@ -422,7 +1161,7 @@ impl Decoder {
         }
     }
 
-    fn forney_syndromes(&self, synd: &[u8], pos: &[u8], msg_len: usize) -> Polynom {
+    pub(crate) fn forney_syndromes(&self, synd: &[u8], pos: &[u8], msg_len: usize) -> Polynom {
         let mut erase_pos_rev = Polynom::with_length(pos.len());
         for (i, x) in pos.iter().enumerate() {
             erase_pos_rev[i] = msg_len as u8 - 1 - x;
@@ -441,6 +1180,96 @@ impl Decoder {
     }
 }
 
+/// Syndromes of a message that is received one symbol at a time
+///
+/// Each call to [`push`](SyndromeState::push) updates all of the syndromes,
+/// so whether the message is corrupted is known as soon as its last symbol
+/// has been pushed.
+#[derive(Debug, Copy, Clone)]
+pub struct SyndromeState {
+    ecc_len: u8,
+    synd: [u8; crate::POLYNOMIAL_MAX_LENGTH - 1],
+    msg_len: usize,
+}
+
+impl SyndromeState {
+    /// Feeds the next symbol of the message.
+    ///
+    /// Returns an error if `symbol` is greater than 31 or if the message
+    /// would be longer than 31 symbols.
+    pub fn push(&mut self, symbol: u8) -> Result<(), UsageError> {
+        if symbol > 31 {
+            return Err(invalid_symbol());
+        }
+        if self.msg_len == 31 {
+            return Err(invalid_combined_len());
+        }
+        for (i, s) in self.synd[..self.ecc_len as usize].iter_mut().enumerate() {
+            *s = gf::mul(*s, gf::EXP[i]) ^ symbol;
+        }
+        self.msg_len += 1;
+        Ok(())
+    }
+
+    /// Number of symbols pushed so far
+    pub fn msg_len(&self) -> usize {
+        self.msg_len
+    }
+
+    /// Syndromes of the symbols pushed so far
+    pub fn syndromes(&self) -> &[u8] {
+        &self.synd[..self.ecc_len as usize]
+    }
+
+    /// Returns `true` if the symbols pushed so far are not a valid message.
+    pub fn is_corrupted(&self) -> bool {
+        self.syndromes().iter().any(|&x| x != 0)
+    }
+}
+
+/// Positions and magnitudes of the errors found by [`Decoder::locate_errors`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ErrorLocations {
+    positions: [u8; crate::POLYNOMIAL_MAX_LENGTH],
+    magnitudes: [u8; crate::POLYNOMIAL_MAX_LENGTH],
+    len: usize,
+}
+
+impl ErrorLocations {
+    /// Positions of the symbols that are wrong, in increasing order
+    pub fn positions(&self) -> &[u8] {
+        &self.positions[..self.len]
+    }
+
+    /// Values that the symbols at [`positions`](ErrorLocations::positions)
+    /// need to be XORed with to correct them
+    pub fn magnitudes(&self) -> &[u8] {
+        &self.magnitudes[..self.len]
+    }
+
+    /// Number of symbols that are wrong
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if none of the symbols are wrong.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Sorts the errors by position
+    fn sort(&mut self) {
+        for i in 1..self.len {
+            let mut j = i;
+            while j > 0 && self.positions[j - 1] > self.positions[j] {
+                self.positions.swap(j - 1, j);
+                self.magnitudes.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+    }
+}
+
 /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
 /// Also includes the number of errors corrected.
 ///
@@ -477,6 +1306,22 @@ pub fn correct_err_count(
     Decoder::new(ecc).correct_err_count(msg, erase_pos)
 }
 
+/// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
+/// Also includes the number of errors corrected.
+///
+/// This is the same as [`correct_err_count`](crate::correct_err_count), except that
+/// the symbols are already known to be valid and aren't checked again.
+pub fn correct_err_count_symbols(
+    msg: &[Symbol],
+    ecc: u8,
+    erase_pos: Option<&[u8]>,
+) -> Result<(Buffer, usize), CorrectionError> {
+    if ecc >= 31 {
+        return Err(invalid_ecc().into());
+    }
+    Decoder::new(ecc).correct_err_count_symbols(msg, erase_pos)
+}
+
 /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
 ///
 /// # Example
@@ -511,6 +1356,39 @@ pub fn correct(
     Decoder::new(ecc).correct(msg, erase_pos)
 }
 
+/// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
+///
+/// This is the same as [`correct`](crate::correct), except that
+/// the symbols are already known to be valid and aren't checked again.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::{encode, correct_symbols, Symbol};
+///
+/// // Encode message
+/// let mut encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+///
+/// // Corrupt message
+/// encoded[2] = 1;
+///
+/// // Decode and correct message,
+/// let symbols = Symbol::slice_from_bytes(&encoded).unwrap();
+/// let corrected = correct_symbols(symbols, 4, None).unwrap();
+///
+/// // Check results
+/// assert_eq!(&[1, 2, 3, 4], corrected.data())
+/// ```
+pub fn correct_symbols(
+    msg: &[Symbol],
+    ecc: u8,
+    erase_pos: Option<&[u8]>,
+) -> Result<Buffer, CorrectionError> {
+    if ecc >= 31 {
+        return Err(invalid_ecc().into());
+    }
+    Decoder::new(ecc).correct_symbols(msg, erase_pos)
+}
+
 /// Performs fast corruption check.
 ///
 /// # Example
@@ -531,12 +1409,24 @@ pub fn correct(
 /// ```
 pub fn is_corrupted(msg: &[u8], ecc: u8) -> Result<bool, UsageError> {
     if ecc >= 31 {
-        return Err(invalid_ecc().into());
+        return Err(invalid_ecc());
     }
     Decoder::new(ecc).is_corrupted(msg)
 }
 
+/// Performs fast corruption check.
+///
+/// This is the same as [`is_corrupted`](crate::is_corrupted), except that
+/// the symbols are already known to be valid and aren't checked again.
+pub fn is_corrupted_symbols(msg: &[Symbol], ecc: u8) -> Result<bool, UsageError> {
+    if ecc >= 31 {
+        return Err(invalid_ecc());
+    }
+    Decoder::new(ecc).is_corrupted_symbols(msg)
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::unnecessary_mut_passed)]
 mod tests {
     use super::*;
     use crate::encode;
@@ -642,6 +1532,46 @@ mod tests {
         assert!(err_pos.is_err());
     }
 
+    #[test]
+    fn error_at_erasure() {
+        // The decoder finds an error at the erased position 9, which can't be
+        // corrected
+        let msg = [31, 1, 7, 27, 8, 11, 27, 16, 13, 0];
+        let erase_pos = [9];
+
+        match Decoder::new(4).correct(&msg, Some(&erase_pos)) {
+            Err(CorrectionError::TooManyErrors) => {},
+            _ => panic!("expected TooManyErrors"),
+        }
+        match correct_err_count(&msg, 4, Some(&erase_pos)) {
+            Err(CorrectionError::TooManyErrors) => {},
+            _ => panic!("expected TooManyErrors"),
+        }
+    }
+
+    #[test]
+    fn errors_and_erasures_limit() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let encoded = encode(&px[..], 8).unwrap();
+
+        // 3 errors and 3 erasures need 9 ECC symbols. This used to be
+        // miscorrected to a different codeword.
+        let msg = [1, 2, 29, 4, 5, 12, 3, 17, 9, 9, 16, 27, 7, 22, 25, 20, 25];
+        match Decoder::new(8).correct(&msg, Some(&[13, 7, 5])) {
+            Err(CorrectionError::TooManyErrors) => {},
+            _ => panic!("expected TooManyErrors"),
+        }
+
+        // 2 errors and 4 erasures fit
+        let mut msg = encoded;
+        for &pos in &[0, 4, 8, 12, 15, 16] {
+            msg[pos] ^= 5;
+        }
+        let (corrected, fixed) = Decoder::new(8).correct_err_count(&msg, Some(&[0, 4, 8, 12])).unwrap();
+        assert_eq!(&encoded[..], &corrected[..]);
+        assert_eq!(6, fixed);
+    }
+
     #[test]
     fn forney_syndromes() {
         let synd = [0, 29, 20, 23, 13, 24, 11];
@@ -667,6 +1597,334 @@ mod tests {
         assert_eq!(result, **decoded);
     }
 
+    #[test]
+    fn decode_symbols() {
+        let msg = [0, 1, 2, 31, 31, 31, 31, 31, 31, 9, 4, 1, 17, 17, 3, 9, 19, 24, 5];
+        let symbols = Symbol::slice_from_bytes(&msg).unwrap();
+        let erase_pos = [3, 4, 5];
+
+        let decoder = Decoder::new(9);
+        assert!(decoder.is_corrupted_symbols(symbols).unwrap());
+
+        let (decoded, fixed) = decoder.correct_err_count_symbols(symbols, Some(&erase_pos)).unwrap();
+        assert_eq!(decoder.correct(&msg, Some(&erase_pos)).unwrap().data(), decoded.data());
+        assert_eq!(6, fixed);
+        assert!(!decoder.is_corrupted_symbols(Symbol::slice_from_bytes(&decoded).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn erase_pos_out_of_range() {
+        let encoded = encode(&[1, 2, 3, 4], 4).unwrap();
+        assert!(Decoder::new(4).correct(&encoded, Some(&[7])).is_ok());
+        assert!(Decoder::new(4).correct(&encoded, Some(&[8])).is_err());
+    }
+
+    #[test]
+    fn syndrome_state() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut encoded = encode(&px[..], 8).unwrap();
+        let decoder = Decoder::new(8);
+
+        let mut state = decoder.syndrome_state();
+        for &x in encoded.iter() {
+            state.push(x).unwrap();
+        }
+        assert!(!state.is_corrupted());
+        assert_eq!(encoded.len(), state.msg_len());
+
+        encoded[5] = 1;
+        encoded[10] = 0;
+
+        let mut state = decoder.syndrome_state();
+        for &x in encoded.iter() {
+            state.push(x).unwrap();
+        }
+        assert!(state.is_corrupted());
+        assert_eq!(&decoder.calc_syndromes(&encoded)[1..], state.syndromes());
+
+        let (corrected, fixed) = decoder.correct_err_count_with_syndromes(&encoded, &state, None).unwrap();
+        assert_eq!(&px, corrected.data());
+        assert_eq!(2, fixed);
+
+        let erase_pos = [5, 7, 12];
+        let expected = decoder.correct_err_count(&encoded, Some(&erase_pos)).unwrap();
+        let (corrected, fixed) = decoder.correct_err_count_with_syndromes(&encoded, &state, Some(&erase_pos)).unwrap();
+        assert_eq!(&expected.0[..], &corrected[..]);
+        assert_eq!(expected.1, fixed);
+
+        assert!(decoder.correct_err_count_with_syndromes(&encoded[1..], &state, None).is_err());
+        assert!(Decoder::new(7).correct_err_count_with_syndromes(&encoded, &state, None).is_err());
+    }
+
+    #[test]
+    fn detached() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let encoded = encode(&px[..], 8).unwrap();
+        let decoder = Decoder::new(8);
+
+        let mut data = px;
+        let mut ecc = [0; 8];
+        ecc.copy_from_slice(encoded.ecc());
+        assert!(!decoder.is_corrupted_detached(&data, &ecc).unwrap());
+        assert_eq!(0, decoder.correct_detached(&mut data, &mut ecc, None).unwrap());
+
+        // Errors in both slices, plus erasures that may or may not be wrong
+        data[0] = 0;
+        data[4] = 31;
+        ecc[6] ^= 3;
+        data[7] = 0;
+        assert!(decoder.is_corrupted_detached(&data, &ecc).unwrap());
+
+        let fixed = decoder.correct_detached(&mut data, &mut ecc, Some(&[7, 8, 15])).unwrap();
+        assert_eq!(5, fixed);
+        assert_eq!(px, data);
+        assert_eq!(encoded.ecc(), &ecc);
+
+        // Too many errors leaves both slices unchanged
+        let mut bad_data = [0, 0, 0, 0, 0, 6, 7, 8, 9];
+        let mut bad_ecc = ecc;
+        bad_ecc[0] ^= 1;
+        assert!(decoder.correct_detached(&mut bad_data, &mut bad_ecc, None).is_err());
+        assert_eq!([0, 0, 0, 0, 0, 6, 7, 8, 9], bad_data);
+        assert_eq!(ecc[0] ^ 1, bad_ecc[0]);
+
+        assert!(decoder.is_corrupted_detached(&data, &ecc[..7]).is_err());
+        assert!(decoder.is_corrupted_detached(&[0; 24], &ecc).is_err());
+        assert!(decoder.correct_detached(&mut data, &mut ecc, Some(&[17])).is_err());
+    }
+
+    #[test]
+    fn layout() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let layout = Layout::with_positions(&[14, 0, 8, 3, 4, 11]).unwrap();
+        let mut msg = [0; 15];
+        crate::encoder::ENCODER_6.encode_with_layout(&px, &layout, &mut msg).unwrap();
+        let expected = msg;
+
+        let decoder = Decoder::new(6);
+        assert_eq!(0, decoder.correct_with_layout(&mut msg, &layout, None).unwrap());
+
+        msg[0] ^= 5;
+        msg[1] = 0;
+        msg[13] = 0;
+        let fixed = decoder.correct_with_layout(&mut msg, &layout, Some(&[13, 1])).unwrap();
+        assert_eq!(3, fixed);
+        assert_eq!(expected, msg);
+
+        let mut msg = [0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8];
+        assert!(decoder.correct_with_layout(&mut msg, &Layout::prefix(), None).is_err());
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 5, 6, 7, 8], msg);
+
+        assert!(decoder.correct_with_layout(&mut msg, &layout, Some(&[15])).is_err());
+
+        // The layout was created for a longer message
+        let layout = Layout::with_positions(&[16, 0, 8, 3, 4, 11]).unwrap();
+        assert!(decoder.correct_with_layout(&mut msg, &layout, None).is_err());
+    }
+
+    #[test]
+    fn locate_errors_with_layout() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let layout = Layout::with_positions(&[14, 0, 8, 3, 4, 11]).unwrap();
+        let mut msg = [0; 15];
+        crate::encoder::ENCODER_6.encode_with_layout(&px, &layout, &mut msg).unwrap();
+
+        let decoder = Decoder::new(6);
+        assert!(decoder.locate_errors_with_layout(&msg, &layout, None).unwrap().is_empty());
+
+        // ECC symbols at 0 and 14, and data symbols at 1 and 13
+        msg[0] ^= 5;
+        msg[1] ^= 7;
+        msg[13] ^= 9;
+        msg[14] ^= 1;
+        let locations = decoder.locate_errors_with_layout(&msg, &layout, Some(&[13, 1])).unwrap();
+        assert_eq!(&[0, 1, 13, 14], locations.positions());
+        assert_eq!(&[5, 7, 9, 1], locations.magnitudes());
+
+        assert!(decoder.locate_errors_with_layout(&msg, &layout, None).is_err());
+        assert!(decoder.locate_errors_with_layout(&msg, &layout, Some(&[15])).is_err());
+    }
+
+    #[test]
+    fn known() {
+        let decoder = Decoder::new(4);
+        let encoded = encode(&[9, 2, 3, 4], 4).unwrap();
+
+        // A codeword of the minimum weight, which is non-zero at 0 and at the
+        // ECC symbols.
+        let diff = encode(&[1, 0, 0, 0], 4).unwrap();
+
+        // 3 errors, which is 2 away from another codeword that differs at 0
+        let mut received = encoded;
+        for i in 4..7 {
+            received[i] ^= diff[i];
+        }
+        let (miscorrected, _) = decoder.correct_err_count(&received, None).unwrap();
+        assert_eq!(8, miscorrected[0]);
+
+        match decoder.correct_with_known(&received, &[0], None) {
+            Err(CorrectionError::TooManyErrors) => {},
+            _ => panic!("expected TooManyErrors"),
+        }
+        match decoder.correct_with_known_prefix(&received[1..], &[9], None) {
+            Err(CorrectionError::TooManyErrors) => {},
+            _ => panic!("expected TooManyErrors"),
+        }
+
+        // Known positions in the ECC symbols are fine as well
+        let mut received = encoded;
+        received[1] = 0;
+        received[2] = 0;
+        let (corrected, fixed) = decoder.correct_with_known(&received, &[0, 7], None).unwrap();
+        assert_eq!(&encoded[..], &corrected[..]);
+        assert_eq!(2, fixed);
+
+        let (corrected, fixed) = decoder.correct_with_known_prefix(&received[2..], &[9, 2], Some(&[0])).unwrap();
+        assert_eq!(&encoded[..], &corrected[..]);
+        assert_eq!(1, fixed);
+
+        assert!(decoder.correct_with_known(&received, &[8], None).is_err());
+        assert!(decoder.correct_with_known(&received, &[1], Some(&[1])).is_err());
+        assert!(decoder.correct_with_known_prefix(&received, &[0; 24], None).is_err());
+        assert!(decoder.correct_with_known_prefix(&received[2..], &[9, 2], Some(&[6])).is_err());
+    }
+
+    #[test]
+    fn max_errors() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let encoded = encode(&px[..], 8).unwrap();
+        let decoder = Decoder::new(8).with_max_errors(2).unwrap();
+
+        let corrupt = |count: usize| {
+            let mut msg = encoded;
+            for x in msg[..count].iter_mut() {
+                *x ^= 1;
+            }
+            msg
+        };
+
+        assert_eq!(2, decoder.correct_err_count(&corrupt(2), None).unwrap().1);
+        assert!(decoder.correct(&corrupt(3), None).is_err());
+        assert!(Decoder::new(8).correct(&corrupt(3), None).is_ok());
+
+        // Erasures count as half an error
+        assert!(decoder.correct(&corrupt(3), Some(&[0, 1])).is_ok());
+        assert!(decoder.correct(&corrupt(3), Some(&[0])).is_err());
+        assert!(decoder.correct(&corrupt(4), Some(&[0, 1, 2, 3])).is_ok());
+        assert!(decoder.correct(&corrupt(5), Some(&[0, 1, 2, 3, 4])).is_err());
+
+        // Up to 6 errors are always detected
+        let mut seed = 12345u32;
+        let mut next = |n: u32| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) % n
+        };
+        for _ in 0..2000 {
+            let mut msg = encoded;
+            for _ in 0..3 + next(4) {
+                let pos = next(msg.len() as u32) as usize;
+                msg[pos] ^= 1 + next(31) as u8;
+            }
+            if let Ok(corrected) = decoder.correct(&msg, None) {
+                assert_eq!(&encoded[..], &corrected[..]);
+            }
+        }
+
+        assert!(Decoder::new(8).with_max_errors(4).is_ok());
+        assert!(Decoder::new(8).with_max_errors(5).is_err());
+        assert!(Decoder::new(7).with_max_errors(4).is_err());
+    }
+
+    #[test]
+    fn recover_erasures() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let encoded = encode(&px[..], 8).unwrap();
+        let decoder = Decoder::new(8);
+
+        let erase_pos = [16, 0, 3, 4, 9, 10, 11, 12];
+        for count in 0..=erase_pos.len() {
+            let mut msg = encoded;
+            for &pos in &erase_pos[..count] {
+                msg[pos as usize] = 31 - msg[pos as usize];
+            }
+            let recovered = decoder.recover_erasures(&msg, &erase_pos[..count]).unwrap();
+            assert_eq!(&encoded[..], &recovered[..]);
+            let expected = decoder.correct(&msg, Some(&erase_pos[..count])).unwrap();
+            assert_eq!(&expected[..], &recovered[..]);
+        }
+
+        // An error that wasn't erased
+        let mut msg = encoded;
+        msg[0] = 0;
+        msg[1] = 0;
+        assert!(decoder.recover_erasures(&msg, &[0]).is_err());
+
+        assert!(decoder.recover_erasures(&msg, &[0, 1, 2, 3, 4, 5, 6, 7, 8]).is_err());
+        assert!(decoder.recover_erasures(&msg, &[17]).is_err());
+        assert!(decoder.recover_erasures(&msg, &[0, 1, 0]).is_err());
+    }
+
+    #[test]
+    fn locate_errors() {
+        let px = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let encoded = encode(&px[..], 8).unwrap();
+        let decoder = Decoder::new(8);
+
+        assert!(decoder.locate_errors(&encoded, None).unwrap().is_empty());
+
+        let mut msg = encoded;
+        msg[14] ^= 3;
+        msg[2] ^= 31;
+        msg[9] ^= 1;
+        let msg_before = msg;
+
+        // The erasure at 5 is already right
+        let locations = decoder.locate_errors(&msg, Some(&[9, 5])).unwrap();
+        assert_eq!(&[2, 9, 14], locations.positions());
+        assert_eq!(&[31, 1, 3], locations.magnitudes());
+        assert_eq!(3, locations.len());
+        assert_eq!(&msg_before[..], &msg[..]);
+
+        // Beyond the limit the number of errors is only an estimate
+        msg[0] ^= 1;
+        msg[1] ^= 1;
+        match decoder.locate_errors(&msg, None) {
+            Err(LocateError::TooManyErrors { estimated_errors }) => assert_eq!(4, estimated_errors),
+            _ => panic!("5 errors can't be located with 8 ECC symbols"),
+        }
+        match decoder.locate_errors(&msg, Some(&[0, 1, 2, 3, 4, 5, 6, 7, 8])) {
+            Err(LocateError::TooManyErasures) => {},
+            _ => panic!("9 erasures can't be corrected with 8 ECC symbols"),
+        }
+
+        assert!(matches!(decoder.locate_errors(&msg, Some(&[17])), Err(LocateError::UsageError(_))));
+        assert!(matches!(decoder.locate_errors(&msg, Some(&[1, 1])), Err(LocateError::UsageError(_))));
+        assert!(matches!(decoder.locate_errors(&msg[..7], None), Err(LocateError::UsageError(_))));
+    }
+
+    #[test]
+    fn parity_check_matrix() {
+        for ecc in 0..=30 {
+            let decoder = Decoder::new(ecc);
+            let encoder = select_encoder(ecc).unwrap();
+            for &data_len in [0, 1, 31 - ecc as usize].iter() {
+                let g = encoder.generator_matrix(data_len).unwrap();
+                let h = decoder.parity_check_matrix(data_len + ecc as usize).unwrap();
+                assert_eq!((ecc as usize, data_len + ecc as usize), (h.rows(), h.cols()));
+                assert!(g.mul(&h.transpose()).unwrap().is_zero());
+            }
+        }
+
+        let h = Decoder::new(4).parity_check_matrix(9).unwrap();
+        let encoded = encode(&[3, 1, 4, 1, 5], 4).unwrap();
+        let mut msg = Matrix::zero(9, 1);
+        for (i, &x) in encoded.iter().enumerate() {
+            msg.set(i, 0, x);
+        }
+        assert!(h.mul(&msg).unwrap().is_zero());
+        assert!(Decoder::new(4).parity_check_matrix(3).is_err());
+    }
+
     #[test]
     fn decode_lots_of_errors() {
         // fifteen 0s followed by fifteen 15s - with 30 error correcting
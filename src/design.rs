@@ -0,0 +1,332 @@
+//! Tools for choosing Reed-Solomon and binary BCH codes of length 31.
+//!
+//! The roots of a cyclic code of length 31 are powers of `α`. A binary BCH
+//! code, whose symbols are single bits, must contain every conjugate `α^(2i)`
+//! of each of its roots `α^i`, so its roots come in whole cyclotomic cosets and
+//! its generator is a product of minimal polynomials. A Reed-Solomon code, whose
+//! symbols are elements of GF(2^5), can use any set of consecutive roots.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::design;
+//! use reed_solomon_32::encoder::ENCODER_4;
+//!
+//! // The encoders in this crate use the roots α^0 to α^(ecc - 1)
+//! let rs = design::reed_solomon(0, 5).unwrap();
+//! assert_eq!((31, 27, 5), (rs.n(), rs.k(), rs.d()));
+//! assert_eq!(ENCODER_4.generator(), *rs.generator());
+//!
+//! // The binary BCH code correcting 2 errors
+//! let bch = design::bch(1, 5).unwrap();
+//! assert_eq!((31, 21, 5), (bch.n(), bch.k(), bch.d()));
+//! ```
+
+use crate::err::{invalid_design, UsageError};
+use crate::{Gf32, Poly};
+
+const N: u8 = 31;
+
+/// Set of exponents `{i, 2i, 4i, ...}` modulo 31
+///
+/// The powers of `α` with these exponents are the roots of the same minimal
+/// polynomial.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CyclotomicCoset {
+    elements: [u8; 5],
+    size: u8,
+}
+
+impl CyclotomicCoset {
+    /// Exponents in the coset, in the order `r, 2r, 4r, ...`, where `r` is
+    /// the [`representative`](CyclotomicCoset::representative)
+    pub fn elements(&self) -> &[u8] {
+        &self.elements[..self.size as usize]
+    }
+
+    /// Smallest exponent in the coset
+    pub fn representative(&self) -> u8 {
+        self.elements[0]
+    }
+
+    /// Number of exponents in the coset, which is 1 for `{0}` and 5 otherwise
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Returns `true` if `i` modulo 31 is in the coset.
+    pub fn contains(&self, i: u8) -> bool {
+        self.elements().contains(&(i % N))
+    }
+}
+
+/// Cyclotomic coset of 2 modulo 31 that contains `i` modulo 31
+pub const fn cyclotomic_coset(i: u8) -> CyclotomicCoset {
+    let mut first = i % N;
+    let mut x = (first as u16 * 2 % N as u16) as u8;
+    while x != i % N {
+        if x < first {
+            first = x;
+        }
+        x = (x as u16 * 2 % N as u16) as u8;
+    }
+
+    let mut coset = CyclotomicCoset {
+        elements: [0; 5],
+        size: 0,
+    };
+    let mut x = first;
+    loop {
+        coset.elements[coset.size as usize] = x;
+        coset.size += 1;
+        x = (x as u16 * 2 % N as u16) as u8;
+        if x == first {
+            return coset;
+        }
+    }
+}
+
+/// All 7 cyclotomic cosets of 2 modulo 31, in increasing order of their representatives
+pub fn cyclotomic_cosets() -> impl Iterator<Item = CyclotomicCoset> {
+    (0..N).filter(|&i| cyclotomic_coset(i).representative() == i).map(cyclotomic_coset)
+}
+
+/// Minimal polynomial of `α^i`, the monic polynomial with binary coefficients
+/// of the smallest degree that has `α^i` as a root
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::{design, Gf32};
+///
+/// // x^5 + x^2 + 1, the polynomial the field is built with
+/// let p = design::minimal_polynomial(1);
+/// assert_eq!(Some(5), p.degree());
+/// assert_eq!(Gf32::ZERO, p.eval(Gf32::ALPHA));
+/// ```
+pub fn minimal_polynomial(i: u8) -> Poly {
+    let coset = cyclotomic_coset(i);
+    coset.elements().iter().fold(Poly::one(), |p, &j| {
+        p * Poly::from_roots(&[Gf32::exp(j as i32)])
+    })
+}
+
+/// Cyclic code of length 31 and its parameters
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Code {
+    generator: Poly,
+    k: u8,
+    d: u8,
+}
+
+impl Code {
+    /// Generator polynomial
+    pub fn generator(&self) -> &Poly {
+        &self.generator
+    }
+
+    /// Length of the codewords, which is always 31
+    pub fn n(&self) -> u8 {
+        N
+    }
+
+    /// Number of data symbols
+    pub fn k(&self) -> u8 {
+        self.k
+    }
+
+    /// Minimum distance. Up to `(d - 1) / 2` errors can be corrected.
+    pub fn d(&self) -> u8 {
+        self.d
+    }
+}
+
+/// Reed-Solomon code over GF(2^5) with the roots `α^first_root` to
+/// `α^(first_root + delta - 2)`
+///
+/// The code has `delta - 1` ECC symbols and a minimum distance of `delta`.
+/// Returns an error if `first_root` is greater than 30 or if `delta` isn't
+/// between 1 and 31.
+pub fn reed_solomon(first_root: u8, delta: u8) -> Result<Code, UsageError> {
+    check_design(first_root, delta)?;
+    let generator = (0..delta - 1).fold(Poly::one(), |p, i| {
+        p * Poly::from_roots(&[Gf32::exp(first_root as i32 + i as i32)])
+    });
+    Ok(Code {
+        generator,
+        k: N - (delta - 1),
+        d: delta,
+    })
+}
+
+/// Binary BCH code with the designed distance `delta`, whose roots include
+/// `α^first_root` to `α^(first_root + delta - 2)`
+///
+/// The generator is the product of the minimal polynomials of those roots,
+/// and the minimum distance, which may be greater than `delta`, is computed
+/// from the weights of the codewords. Returns an error if `first_root` is
+/// greater than 30, if `delta` isn't between 1 and 31 or if the only codeword
+/// would be 0.
+pub fn bch(first_root: u8, delta: u8) -> Result<Code, UsageError> {
+    check_design(first_root, delta)?;
+    let generator = cyclotomic_cosets()
+        .filter(|coset| (0..delta - 1).any(|i| coset.contains(((first_root as u16 + i as u16) % N as u16) as u8)))
+        .fold(Poly::one(), |p, coset| p * minimal_polynomial(coset.representative()));
+
+    let k = N - generator.degree().unwrap() as u8;
+    if k == 0 {
+        return Err(invalid_design());
+    }
+    Ok(Code {
+        generator,
+        k,
+        d: binary_min_distance(&generator),
+    })
+}
+
+fn check_design(first_root: u8, delta: u8) -> Result<(), UsageError> {
+    if first_root >= N || delta == 0 || delta > N {
+        return Err(invalid_design());
+    }
+    Ok(())
+}
+
+// Bit i is the coefficient of x^i
+fn to_bits(p: &Poly) -> u64 {
+    (0..64).filter(|&i| p.coefficient(i) == Gf32::ONE).fold(0, |bits, i| bits | 1 << i)
+}
+
+// Counts the codewords of each weight of the binary code spanned by the first
+// `dimension` shifts of `basis`
+fn weights(basis: u64, dimension: u8, counts: &mut [i64; N as usize + 1]) {
+    // Visit every codeword in Gray code order, so each one differs from the
+    // previous one by a single shifted copy of the generator.
+    let mut codeword = 0u64;
+    counts[0] += 1;
+    for i in 1..1u64 << dimension {
+        codeword ^= basis << i.trailing_zeros();
+        counts[codeword.count_ones() as usize] += 1;
+    }
+}
+
+// Minimum distance of the binary cyclic code of length 31 generated by `generator`
+fn binary_min_distance(generator: &Poly) -> u8 {
+    let g = to_bits(generator);
+    let g_degree = 63 - g.leading_zeros() as u8;
+    let k = N - g_degree;
+    let mut counts = [0; N as usize + 1];
+
+    if k <= N - k {
+        weights(g, k, &mut counts);
+    } else {
+        // Enumerate the smaller dual code instead. It is generated by the
+        // reverse of h = (x^31 + 1) / g, and since reversing doesn't change
+        // weights, h itself will do. The MacWilliams identity then gives the
+        // number of codewords of each weight of the code itself.
+        let mut remainder = 1u64 << N | 1;
+        let mut h = 0u64;
+        while remainder != 0 && 63 - remainder.leading_zeros() as u8 >= g_degree {
+            let shift = 63 - remainder.leading_zeros() as u8 - g_degree;
+            h |= 1 << shift;
+            remainder ^= g << shift;
+        }
+        let mut dual = [0; N as usize + 1];
+        weights(h, N - k, &mut dual);
+        for (w, count) in counts.iter_mut().enumerate() {
+            let sum: i64 = dual.iter().enumerate().map(|(j, &b)| b * krawtchouk(w as i64, j as i64)).sum();
+            *count = sum >> (N - k);
+        }
+    }
+    counts.iter().skip(1).position(|&count| count != 0).unwrap() as u8 + 1
+}
+
+// Krawtchouk polynomial K_w(j) for binary codes of length 31
+fn krawtchouk(w: i64, j: i64) -> i64 {
+    (0..=w).map(|s| {
+        let sign = if s % 2 == 0 { 1 } else { -1 };
+        sign * binomial(j, s) * binomial(N as i64 - j, w - s)
+    }).sum()
+}
+
+fn binomial(n: i64, k: i64) -> i64 {
+    if k < 0 || k > n {
+        return 0;
+    }
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder_impl::select_encoder;
+
+    #[test]
+    fn cosets() {
+        let cosets = [
+            &[0][..],
+            &[1, 2, 4, 8, 16],
+            &[3, 6, 12, 24, 17],
+            &[5, 10, 20, 9, 18],
+            &[7, 14, 28, 25, 19],
+            &[11, 22, 13, 26, 21],
+            &[15, 30, 29, 27, 23],
+        ];
+        assert_eq!(7, cyclotomic_cosets().count());
+        for (coset, &expected) in cyclotomic_cosets().zip(cosets.iter()) {
+            assert_eq!(expected, coset.elements());
+        }
+        assert_eq!(cyclotomic_coset(3), cyclotomic_coset(24));
+        assert_eq!(3, cyclotomic_coset(24).representative());
+        assert!(cyclotomic_coset(31).contains(0));
+    }
+
+    #[test]
+    fn minimal_polynomials() {
+        // x^31 + 1 is the product of all of the minimal polynomials
+        let product = cyclotomic_cosets().fold(Poly::one(), |p, coset| p * minimal_polynomial(coset.representative()));
+        assert_eq!(Poly::monomial(Gf32::ONE, 31) + Poly::one(), product);
+
+        for i in 0..31 {
+            let p = minimal_polynomial(i);
+            assert_eq!(Gf32::ZERO, p.eval(Gf32::exp(i as i32)));
+            assert_eq!(Some(cyclotomic_coset(i).size()), p.degree());
+            assert!(p.coefficients().all(|c| c == Gf32::ZERO || c == Gf32::ONE));
+        }
+    }
+
+    #[test]
+    fn encoders() {
+        for ecc in 0..=30 {
+            let code = reed_solomon(0, ecc + 1).unwrap();
+            assert_eq!(select_encoder(ecc).unwrap().generator(), *code.generator());
+            assert_eq!(31 - ecc, code.k());
+            assert_eq!(ecc + 1, code.d());
+        }
+        assert!(reed_solomon(31, 3).is_err());
+        assert!(reed_solomon(0, 0).is_err());
+        assert!(reed_solomon(0, 32).is_err());
+
+        let code = reed_solomon(29, 5).unwrap();
+        for i in [29, 30, 0, 1].iter() {
+            assert_eq!(Gf32::ZERO, code.generator().eval(Gf32::exp(*i)));
+        }
+    }
+
+    #[test]
+    fn bch_codes() {
+        // Narrow-sense binary BCH codes of length 31
+        let expected = [(1, 31, 1), (3, 26, 3), (5, 21, 5), (7, 16, 7), (9, 11, 11), (11, 11, 11), (13, 6, 15), (15, 6, 15)];
+        for &(delta, k, d) in expected.iter() {
+            let code = bch(1, delta).unwrap();
+            assert_eq!((k, d), (code.k(), code.d()), "delta = {}", delta);
+        }
+
+        // The repetition code
+        let code = bch(1, 31).unwrap();
+        assert_eq!((1, 31), (code.k(), code.d()));
+
+        // Roots α^0 and α^1 add the parity bit to the Hamming code
+        let code = bch(0, 3).unwrap();
+        assert_eq!((25, 4), (code.k(), code.d()));
+
+        assert!(bch(0, 31).is_err());
+    }
+}
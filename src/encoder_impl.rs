@@ -1,7 +1,11 @@
 use crate::gf::poly::Polynom;
 use crate::buffer::Buffer;
-use crate::err::{invalid_combined_len, invalid_data_len, invalid_ecc, invalid_symbol, UsageError};
+use crate::layout::Layout;
+use crate::matrix::Matrix;
+use crate::err::{invalid_codeword_len, invalid_combined_len, invalid_data_len, invalid_ecc, invalid_index, invalid_output_len, invalid_symbol, UsageError};
 use crate::gf;
+use crate::symbol::Symbol;
+use crate::{Gf32, Poly};
 
 /// [`Encoder`] for messages with 0 ECC symbols
 pub const ENCODER_0: Encoder = Encoder::new(polynom![1]);
@@ -125,15 +129,90 @@ impl Encoder {
     /// println!("ecc:   {:?}", encoded.ecc());
     /// ```
     pub fn encode(&self, data: &[u8]) -> Result<Buffer, UsageError> {
+        if data.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        self.encode_unchecked(data)
+    }
+
+    /// Encodes passed `&[Symbol]` slice and returns `Buffer` with result.
+    ///
+    /// This is the same as [`encode`](Encoder::encode), except that
+    /// the symbols are already known to be valid and aren't checked again.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::Symbol;
+    /// use reed_solomon_32::encoder::ENCODER_8;
+    ///
+    /// let data = Symbol::slice_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+    ///
+    /// let encoded = ENCODER_8.encode_symbols(data).unwrap();
+    ///
+    /// assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], encoded.data());
+    /// ```
+    pub fn encode_symbols(&self, data: &[Symbol]) -> Result<Buffer, UsageError> {
+        self.encode_unchecked(Symbol::slice_as_bytes(data))
+    }
+
+    /// Generator polynomial of the code, `(x - α^0)(x - α^1)...(x - α^(ecc - 1))`
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::{Gf32, Poly};
+    /// use reed_solomon_32::encoder::ENCODER_2;
+    ///
+    /// let roots = [Gf32::exp(0), Gf32::exp(1)];
+    /// assert_eq!(Poly::from_roots(&roots), ENCODER_2.generator());
+    /// ```
+    pub fn generator(&self) -> Poly {
+        let mut coefficients = [Gf32::ZERO; crate::POLYNOMIAL_MAX_LENGTH];
+        for (x, &c) in coefficients.iter_mut().zip(self.generator.iter()) {
+            *x = Gf32::new(c).unwrap();
+        }
+        Poly::from_coefficients(&coefficients[..self.generator.len()]).unwrap()
+    }
+
+    /// Generator matrix in systematic form `[I | P]` for messages with
+    /// `data_len` data symbols
+    ///
+    /// Row `i` is the encoded message whose only nonzero data symbol is a 1 at
+    /// position `i`, so multiplying the data by the matrix gives the encoded
+    /// message. See [`Matrix::dual`] for the generator matrix of the dual code.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    ///
+    /// let g = ENCODER_4.generator_matrix(3).unwrap();
+    /// assert_eq!((3, 7), (g.rows(), g.cols()));
+    /// assert_eq!(&ENCODER_4.encode(&[0, 1, 0]).unwrap()[..], g.row(1));
+    /// ```
+    pub fn generator_matrix(&self, data_len: usize) -> Result<Matrix, UsageError> {
+        let ecc_len = self.generator.len() - 1;
+        if data_len + ecc_len > 31 {
+            return Err(invalid_combined_len());
+        }
+        let mut g = Matrix::zero(data_len, data_len + ecc_len);
+        let mut data = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        for row in 0..data_len {
+            data[row] = 1;
+            let encoded = self.encode_unchecked(&data[..data_len])?;
+            for (col, &x) in encoded.iter().enumerate() {
+                g.set(row, col, x);
+            }
+            data[row] = 0;
+        }
+        Ok(g)
+    }
+
+    fn encode_unchecked(&self, data: &[u8]) -> Result<Buffer, UsageError> {
         if data.len() > 31 {
             return Err(invalid_data_len());
         }
         if data.len() + self.generator.len() - 1 > 31 {
             return Err(invalid_combined_len());
         }
-        if data.iter().any(|&x| x > 31) {
-            return Err(invalid_symbol());
-        }
 
         let mut data_out = Polynom::from(data);
         let data_len = data.len();
@@ -151,7 +230,7 @@ impl Encoder {
             if coef != 0 {
                 let lcoef = gf::LOG[coef as usize] as usize;
                 for j in 1..gen.len() {
-                    data_out[i + j] ^= gf::EXP[(lcoef + lgen[j] as usize)];
+                    data_out[i + j] ^= gf::EXP[lcoef + lgen[j] as usize];
                 }
             }
         }
@@ -159,8 +238,231 @@ impl Encoder {
         data_out[..data_len].copy_from_slice(data);
         Ok(Buffer::from_polynom(data_out, data_len))
     }
+
+    /// Updates the error correction code `ecc` of a message with `data_len`
+    /// data symbols after the data symbol at `index` changed from `old` to `new`.
+    ///
+    /// Since the code is linear, the change to the error correction code only
+    /// depends on the difference between `old` and `new` and on `index`, so
+    /// the rest of the data isn't needed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    ///
+    /// let mut data = [1, 2, 3, 4, 5, 6];
+    /// let mut ecc = [0; 4];
+    /// ecc.copy_from_slice(ENCODER_4.encode(&data).unwrap().ecc());
+    ///
+    /// ENCODER_4.update_ecc(&mut ecc, data.len(), 2, data[2], 17).unwrap();
+    /// data[2] = 17;
+    ///
+    /// assert_eq!(ENCODER_4.encode(&data).unwrap().ecc(), &ecc);
+    /// ```
+    pub fn update_ecc(&self,
+                      ecc: &mut [u8],
+                      data_len: usize,
+                      index: usize,
+                      old: u8,
+                      new: u8)
+                      -> Result<(), UsageError> {
+        if ecc.len() != self.generator.len() - 1 {
+            return Err(invalid_codeword_len());
+        }
+        if data_len + ecc.len() > 31 {
+            return Err(invalid_combined_len());
+        }
+        if index >= data_len {
+            return Err(invalid_index());
+        }
+        if old > 31 || new > 31 || ecc.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+
+        // The change is the error correction code of a message whose only
+        // non-zero symbol is the difference, at the same position.
+        let mut state = self.state();
+        state.push(old ^ new)?;
+        for _ in index + 1..data_len {
+            state.push(0)?;
+        }
+        for (x, delta) in ecc.iter_mut().zip(state.finish()) {
+            *x ^= delta;
+        }
+        Ok(())
+    }
+
+    /// Computes the ECC symbols of `data` without copying the data into a `Buffer`.
+    ///
+    /// `N` must be the number of ECC symbols of the `Encoder` constant that was used.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    ///
+    /// let data = [1, 2, 3, 4];
+    /// let ecc: [u8; 4] = ENCODER_4.parity(&data).unwrap();
+    ///
+    /// assert_eq!(ENCODER_4.encode(&data).unwrap().ecc(), &ecc);
+    /// ```
+    pub fn parity<const N: usize>(&self, data: &[u8]) -> Result<[u8; N], UsageError> {
+        if N != self.generator.len() - 1 {
+            return Err(invalid_output_len());
+        }
+        if data.len() + N > 31 {
+            return Err(invalid_combined_len());
+        }
+
+        let mut state = self.state();
+        for &x in data {
+            state.push(x)?;
+        }
+
+        let mut ecc = [0; N];
+        for (x, parity) in ecc.iter_mut().zip(state.finish()) {
+            *x = parity;
+        }
+        Ok(ecc)
+    }
+
+    /// Encodes `data` into `out`, placing the data and ECC symbols according to `layout`.
+    ///
+    /// `out` must be exactly as long as the data and ECC symbols together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::Layout;
+    /// use reed_solomon_32::encoder::ENCODER_2;
+    ///
+    /// let layout = Layout::with_positions(&[0, 3]).unwrap();
+    /// let mut out = [0; 6];
+    /// ENCODER_2.encode_with_layout(&[1, 2, 3, 4], &layout, &mut out).unwrap();
+    ///
+    /// let ecc = ENCODER_2.encode(&[1, 2, 3, 4]).unwrap();
+    /// assert_eq!([ecc[4], 1, 2, ecc[5], 3, 4], out);
+    /// ```
+    pub fn encode_with_layout(&self, data: &[u8], layout: &Layout, out: &mut [u8]) -> Result<(), UsageError> {
+        let ecc_len = self.generator.len() - 1;
+        if data.len() + ecc_len > 31 {
+            return Err(invalid_combined_len());
+        }
+        if out.len() != data.len() + ecc_len {
+            return Err(invalid_output_len());
+        }
+        layout.check(data.len(), ecc_len)?;
+
+        let mut state = self.state();
+        for (i, &x) in data.iter().enumerate() {
+            state.push(x)?;
+            out[layout.physical_index_unchecked(i, data.len(), ecc_len)] = x;
+        }
+        for (i, x) in state.finish().enumerate() {
+            out[layout.physical_index_unchecked(data.len() + i, data.len(), ecc_len)] = x;
+        }
+        Ok(())
+    }
+
+    /// Creates [`EncoderState`] for encoding a message one symbol at a time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use reed_solomon_32::encoder::ENCODER_4;
+    ///
+    /// let mut state = ENCODER_4.state();
+    /// for &x in [1, 2, 3, 4].iter() {
+    ///     state.push(x).unwrap();
+    /// }
+    /// let parity = state.finish();
+    ///
+    /// let encoded = ENCODER_4.encode(&[1, 2, 3, 4]).unwrap();
+    /// assert!(parity.eq(encoded.ecc().iter().copied()));
+    /// ```
+    pub fn state(&self) -> EncoderState<'_> {
+        EncoderState {
+            generator: &self.generator,
+            register: [0; crate::POLYNOMIAL_MAX_LENGTH - 1],
+            data_len: 0,
+        }
+    }
 }
 
+/// State of an [`Encoder`] that is fed one symbol at a time
+///
+/// Only the shift register holding the ECC symbols computed so far is kept,
+/// so the data symbols don't need to be buffered. Once all data symbols have
+/// been pushed, [`finish`](EncoderState::finish) returns the ECC symbols.
+#[derive(Debug, Clone)]
+pub struct EncoderState<'a> {
+    generator: &'a Polynom,
+    register: [u8; crate::POLYNOMIAL_MAX_LENGTH - 1],
+    data_len: usize,
+}
+
+impl EncoderState<'_> {
+    /// Feeds the next data symbol into the encoder.
+    ///
+    /// Returns an error if `symbol` is greater than 31 or if the message
+    /// together with the ECC symbols would be longer than 31 symbols.
+    pub fn push(&mut self, symbol: u8) -> Result<(), UsageError> {
+        if symbol > 31 {
+            return Err(invalid_symbol());
+        }
+        let ecc_len = self.generator.len() - 1;
+        if self.data_len + ecc_len >= 31 {
+            return Err(invalid_combined_len());
+        }
+
+        let register = &mut self.register[..ecc_len];
+        let feedback = if ecc_len == 0 { 0 } else { symbol ^ register[0] };
+        for j in 0..ecc_len {
+            let next = if j + 1 < ecc_len { register[j + 1] } else { 0 };
+            register[j] = next ^ gf::mul(feedback, self.generator[j + 1]);
+        }
+        self.data_len += 1;
+        Ok(())
+    }
+
+    /// Number of data symbols pushed so far
+    pub fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Finishes the message and returns its ECC symbols.
+    pub fn finish(self) -> Parity {
+        Parity {
+            register: self.register,
+            pos: 0,
+            len: self.generator.len() - 1,
+        }
+    }
+}
+
+/// Iterator over the ECC symbols returned by [`EncoderState::finish`]
+#[derive(Debug, Clone)]
+pub struct Parity {
+    register: [u8; crate::POLYNOMIAL_MAX_LENGTH - 1],
+    pos: usize,
+    len: usize,
+}
+
+impl Iterator for Parity {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.pos == self.len {
+            return None;
+        }
+        self.pos += 1;
+        Some(self.register[self.pos - 1])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len - self.pos, Some(self.len - self.pos))
+    }
+}
+
+impl ExactSizeIterator for Parity { }
+
 /// Encodes passed `&[u8]` slice and returns `Buffer` with result using
 /// `ecc` error correcting symbols.
 ///
@@ -177,38 +479,62 @@ impl Encoder {
 /// println!("ecc:   {:?}", encoded.ecc());
 /// ```
 pub fn encode(data: &[u8], ecc: u8) -> Result<Buffer, UsageError> {
+    select_encoder(ecc)?.encode(data)
+}
+
+/// Encodes passed `&[Symbol]` slice and returns `Buffer` with result using
+/// `ecc` error correcting symbols.
+///
+/// This is the same as [`encode`](crate::encode), except that
+/// the symbols are already known to be valid and aren't checked again.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::{encode_symbols, Symbol};
+///
+/// let data = Symbol::slice_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+///
+/// let encoded = encode_symbols(data, 8).unwrap();
+///
+/// assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], encoded.data());
+/// ```
+pub fn encode_symbols(data: &[Symbol], ecc: u8) -> Result<Buffer, UsageError> {
+    select_encoder(ecc)?.encode_symbols(data)
+}
+
+pub(crate) fn select_encoder(ecc: u8) -> Result<&'static Encoder, UsageError> {
     match ecc {
-        0 => ENCODER_0.encode(data),
-        1 => ENCODER_1.encode(data),
-        2 => ENCODER_2.encode(data),
-        3 => ENCODER_3.encode(data),
-        4 => ENCODER_4.encode(data),
-        5 => ENCODER_5.encode(data),
-        6 => ENCODER_6.encode(data),
-        7 => ENCODER_7.encode(data),
-        8 => ENCODER_8.encode(data),
-        9 => ENCODER_9.encode(data),
-        10 => ENCODER_10.encode(data),
-        11 => ENCODER_11.encode(data),
-        12 => ENCODER_12.encode(data),
-        13 => ENCODER_13.encode(data),
-        14 => ENCODER_14.encode(data),
-        15 => ENCODER_15.encode(data),
-        16 => ENCODER_16.encode(data),
-        17 => ENCODER_17.encode(data),
-        18 => ENCODER_18.encode(data),
-        19 => ENCODER_19.encode(data),
-        20 => ENCODER_20.encode(data),
-        21 => ENCODER_21.encode(data),
-        22 => ENCODER_22.encode(data),
-        23 => ENCODER_23.encode(data),
-        24 => ENCODER_24.encode(data),
-        25 => ENCODER_25.encode(data),
-        26 => ENCODER_26.encode(data),
-        27 => ENCODER_27.encode(data),
-        28 => ENCODER_28.encode(data),
-        29 => ENCODER_29.encode(data),
-        30 => ENCODER_30.encode(data),
+        0 => Ok(&ENCODER_0),
+        1 => Ok(&ENCODER_1),
+        2 => Ok(&ENCODER_2),
+        3 => Ok(&ENCODER_3),
+        4 => Ok(&ENCODER_4),
+        5 => Ok(&ENCODER_5),
+        6 => Ok(&ENCODER_6),
+        7 => Ok(&ENCODER_7),
+        8 => Ok(&ENCODER_8),
+        9 => Ok(&ENCODER_9),
+        10 => Ok(&ENCODER_10),
+        11 => Ok(&ENCODER_11),
+        12 => Ok(&ENCODER_12),
+        13 => Ok(&ENCODER_13),
+        14 => Ok(&ENCODER_14),
+        15 => Ok(&ENCODER_15),
+        16 => Ok(&ENCODER_16),
+        17 => Ok(&ENCODER_17),
+        18 => Ok(&ENCODER_18),
+        19 => Ok(&ENCODER_19),
+        20 => Ok(&ENCODER_20),
+        21 => Ok(&ENCODER_21),
+        22 => Ok(&ENCODER_22),
+        23 => Ok(&ENCODER_23),
+        24 => Ok(&ENCODER_24),
+        25 => Ok(&ENCODER_25),
+        26 => Ok(&ENCODER_26),
+        27 => Ok(&ENCODER_27),
+        28 => Ok(&ENCODER_28),
+        29 => Ok(&ENCODER_29),
+        30 => Ok(&ENCODER_30),
         _ => Err(invalid_ecc()),
     }
 }
@@ -264,6 +590,77 @@ mod tests {
         assert_eq!(&super::ENCODER_30.generator[..], &generator_poly(30)[..]);
     }
 
+    #[test]
+    fn state() {
+        let data = [31, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        for ecc in 0..=30 {
+            let encoder = super::select_encoder(ecc).unwrap();
+            let data = &data[..30 - ecc as usize];
+
+            let mut state = encoder.state();
+            for &x in data.iter() {
+                state.push(x).unwrap();
+            }
+            assert!(state.clone().push(0).is_ok());
+            assert_eq!(data.len(), state.data_len());
+
+            let encoded = encoder.encode(data).unwrap();
+            let parity = state.finish();
+            assert_eq!(ecc as usize, parity.len());
+            assert!(parity.eq(encoded.ecc().iter().copied()));
+        }
+    }
+
+    #[test]
+    fn state_limits() {
+        let mut state = super::ENCODER_8.state();
+        assert!(state.push(32).is_err());
+        for _ in 0..23 {
+            state.push(1).unwrap();
+        }
+        assert!(state.push(1).is_err());
+    }
+
+    #[test]
+    fn update_ecc() {
+        let mut data = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3, 2, 3, 8, 4, 6, 2, 6, 4, 3, 3, 8, 3, 2, 7];
+        for ecc_len in 1..=30 {
+            let encoder = super::select_encoder(ecc_len).unwrap();
+            let data = &mut data[..31 - ecc_len as usize];
+            let mut ecc = [0; 30];
+            let ecc = &mut ecc[..ecc_len as usize];
+            ecc.copy_from_slice(encoder.encode(data).unwrap().ecc());
+
+            for index in 0..data.len() {
+                let new = (data[index] * 7 + index as u8) % 32;
+                encoder.update_ecc(ecc, data.len(), index, data[index], new).unwrap();
+                data[index] = new;
+                assert_eq!(encoder.encode(data).unwrap().ecc(), &ecc[..]);
+            }
+        }
+    }
+
+    #[test]
+    fn update_ecc_invalid() {
+        let mut ecc = [0; 4];
+        assert!(super::ENCODER_4.update_ecc(&mut ecc[..3], 4, 0, 1, 2).is_err());
+        assert!(super::ENCODER_4.update_ecc(&mut ecc, 28, 0, 1, 2).is_err());
+        assert!(super::ENCODER_4.update_ecc(&mut ecc, 4, 4, 1, 2).is_err());
+        assert!(super::ENCODER_4.update_ecc(&mut ecc, 4, 0, 1, 32).is_err());
+        assert!(super::ENCODER_4.update_ecc(&mut ecc, 4, 3, 1, 2).is_ok());
+    }
+
+    #[test]
+    fn parity() {
+        let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19];
+        let ecc: [u8; 8] = super::ENCODER_8.parity(&data).unwrap();
+        assert_eq!([5, 10, 26, 18, 9, 22, 13, 21], ecc);
+
+        assert!(super::ENCODER_8.parity::<7>(&data).is_err());
+        assert!(super::ENCODER_8.parity::<8>(&[0; 24]).is_err());
+        assert!(super::ENCODER_8.parity::<8>(&[32]).is_err());
+    }
+
     #[test]
     fn encode() {
         let data = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19];
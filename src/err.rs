@@ -1,3 +1,4 @@
+#[allow(clippy::enum_variant_names)]
 enum UsageErrorCode {
     InvalidEcc,
     InvalidDataLen,
@@ -5,6 +6,20 @@ enum UsageErrorCode {
     InvalidCombinedLen,
     InvalidSymbol,
     InvalidErasePos,
+    InvalidCodewordLen,
+    InvalidPadding,
+    InvalidOutputLen,
+    InvalidIndex,
+    InvalidLayout,
+    InvalidCode,
+    InvalidKnownPos,
+    InvalidMaxErrors,
+    InvalidDegree,
+    InvalidPoints,
+    InvalidDesign,
+    InvalidDimensions,
+    InvalidProbability,
+    InvalidSeparator,
 }
 
 pub struct UsageErrorMessage {
@@ -26,6 +41,34 @@ impl core::fmt::Debug for UsageErrorMessage {
                 write!(f, "Invalid symbol. All symbols must be be in the range [0, 31]."),
             UsageErrorCode::InvalidErasePos =>
                 write!(f, "One of the erasure positions was greater than the message size."),
+            UsageErrorCode::InvalidCodewordLen =>
+                write!(f, "The length of the message does not match the length of the codeword."),
+            UsageErrorCode::InvalidPadding =>
+                write!(f, "The symbols end with padding bits that are not 0 or with more padding than needed."),
+            UsageErrorCode::InvalidOutputLen =>
+                write!(f, "The output buffer is too small."),
+            UsageErrorCode::InvalidIndex =>
+                write!(f, "The index is outside of the data."),
+            UsageErrorCode::InvalidLayout =>
+                write!(f, "The parity positions must be unique, inside the message and match the number of ECC symbols."),
+            UsageErrorCode::InvalidCode =>
+                write!(f, "The evaluation points must be distinct, the multipliers must be nonzero and there must be one of each per symbol."),
+            UsageErrorCode::InvalidKnownPos =>
+                write!(f, "One of the known positions was greater than the message size or was also an erasure."),
+            UsageErrorCode::InvalidMaxErrors =>
+                write!(f, "The maximum number of errors must not be greater than half the number of ECC symbols."),
+            UsageErrorCode::InvalidDegree =>
+                write!(f, "The degree of the polynomial is greater than 63."),
+            UsageErrorCode::InvalidPoints =>
+                write!(f, "The points must be distinct and there must be one value per point."),
+            UsageErrorCode::InvalidDesign =>
+                write!(f, "The designed distance must be between 1 and 31, the first root must be less than 31 and the code must not be empty."),
+            UsageErrorCode::InvalidDimensions =>
+                write!(f, "The dimensions or the form of the matrix are not valid for the operation."),
+            UsageErrorCode::InvalidProbability =>
+                write!(f, "Probabilities must be between 0 and 1 and must not add up to more than 1."),
+            UsageErrorCode::InvalidSeparator =>
+                write!(f, "The separator must be an ASCII character that isn't part of the alphabet."),
         }
     }
 }
@@ -81,6 +124,62 @@ pub fn invalid_erase_pos() -> UsageError {
     UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidErasePos })
 }
 
+pub fn invalid_codeword_len() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidCodewordLen })
+}
+
+pub fn invalid_padding() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidPadding })
+}
+
+pub fn invalid_output_len() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidOutputLen })
+}
+
+pub fn invalid_index() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidIndex })
+}
+
+pub fn invalid_layout() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidLayout })
+}
+
+pub fn invalid_code() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidCode })
+}
+
+pub fn invalid_known_pos() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidKnownPos })
+}
+
+pub fn invalid_max_errors() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidMaxErrors })
+}
+
+pub fn invalid_degree() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidDegree })
+}
+
+pub fn invalid_points() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidPoints })
+}
+
+pub fn invalid_design() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidDesign })
+}
+
+pub fn invalid_dimensions() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidDimensions })
+}
+
+pub fn invalid_probability() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidProbability })
+}
+
+pub fn invalid_separator() -> UsageError {
+    UsageError(UsageErrorMessage { error_code: UsageErrorCode::InvalidSeparator })
+}
+
 /// And error occurred while attempting to correct a message.
 pub enum CorrectionError {
     /// The message had too many errors and they could not be corrected
@@ -116,3 +215,60 @@ impl core::fmt::Display for CorrectionError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for CorrectionError { }
+
+/// An error occurred while attempting to locate the errors of a message.
+pub enum LocateError {
+    /// The message had too many errors and they could not be located.
+    ///
+    /// `estimated_errors` is the degree of the error locator polynomial, not
+    /// counting the erasures. It is usually at least half of the ECC symbols
+    /// that aren't used by erasures, but it doesn't have to match the actual
+    /// number of errors.
+    TooManyErrors { estimated_errors: usize },
+
+    /// There were more erasures than the ECC symbols can correct, so the errors
+    /// weren't searched for.
+    TooManyErasures,
+
+    /// An invalid parameter value was passed to the function. Format the
+    /// `UsageErrorMessage` in order to get more details about the error.
+    UsageError(UsageErrorMessage),
+}
+
+impl From<UsageError> for LocateError {
+    fn from(UsageError(message): UsageError) -> LocateError {
+        LocateError::UsageError(message)
+    }
+}
+
+impl From<LocateError> for CorrectionError {
+    fn from(err: LocateError) -> CorrectionError {
+        match err {
+            LocateError::TooManyErrors { .. } => CorrectionError::TooManyErrors,
+            LocateError::TooManyErasures => CorrectionError::TooManyErrors,
+            LocateError::UsageError(message) => CorrectionError::UsageError(message),
+        }
+    }
+}
+
+impl core::fmt::Debug for LocateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LocateError::TooManyErrors { estimated_errors } =>
+                write!(f, "The message has too many errors to locate them (an estimated {})", estimated_errors),
+            LocateError::TooManyErasures =>
+                write!(f, "The message has too many erasures to locate its errors"),
+            LocateError::UsageError(err) =>
+                write!(f, "Usage error: {}", err),
+        }
+    }
+}
+
+impl core::fmt::Display for LocateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LocateError { }
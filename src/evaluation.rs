@@ -0,0 +1,253 @@
+//! Non-systematic Reed-Solomon codes in evaluation form.
+//!
+//! Instead of appending ECC symbols to the data, the data symbols are treated as
+//! the coefficients of a polynomial `m(x)`, highest degree first, and the
+//! codeword is `m(α^0), m(α^1), ..., m(α^(n-1))`. None of the data symbols
+//! appear in the codeword as is, so [`decode`] interpolates them back after
+//! correcting the codeword.
+//!
+//! Codewords have the same limits as the systematic codes: at most 31 symbols,
+//! of which fewer than 31 are ECC symbols. Any `ecc` errors and erasures, with
+//! each error counting twice, can be corrected.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::evaluation::{decode, encode};
+//!
+//! let data = [1, 2, 3, 4];
+//! let mut codeword = [0; 8];
+//! encode(&data, 4, &mut codeword).unwrap();
+//!
+//! // Corrupt two symbols
+//! codeword[0] ^= 7;
+//! codeword[5] = 0;
+//!
+//! let mut decoded = [0; 4];
+//! assert_eq!(2, decode(&codeword, 4, None, &mut decoded).unwrap());
+//! assert_eq!(data, decoded);
+//! ```
+//!
+//! Internally, scaling symbol `j` of a codeword by `1 / Π (α^j - α^i)` over all
+//! other positions `i` and reversing the result gives a codeword of the
+//! systematic code with the same number of ECC symbols, so the regular decoder
+//! does the actual correction.
+
+use crate::decoder_impl::correct_err_count;
+use crate::err::{invalid_combined_len, invalid_data_len, invalid_ecc, invalid_erase_pos, invalid_output_len, invalid_symbol, CorrectionError, UsageError};
+use crate::gf;
+use crate::gf::poly::Polynom;
+use crate::gf::poly_math::*;
+
+/// Encodes `data` into `out`, which must be exactly `data.len() + ecc` symbols long.
+///
+/// Symbol `j` of the codeword is `data` evaluated as a polynomial at `α^j`.
+pub fn encode(data: &[u8], ecc: u8, out: &mut [u8]) -> Result<(), UsageError> {
+    if ecc >= 31 {
+        return Err(invalid_ecc());
+    }
+    if data.len() > 31 {
+        return Err(invalid_data_len());
+    }
+    if data.len() + ecc as usize > 31 {
+        return Err(invalid_combined_len());
+    }
+    if data.iter().any(|&x| x > 31) {
+        return Err(invalid_symbol());
+    }
+    if out.len() != data.len() + ecc as usize {
+        return Err(invalid_output_len());
+    }
+
+    for (j, x) in out.iter_mut().enumerate() {
+        *x = if data.is_empty() { 0 } else { data.eval(gf::EXP[j]) };
+    }
+    Ok(())
+}
+
+/// Checks if `msg` is corrupted.
+pub fn is_corrupted(msg: &[u8], ecc: u8) -> Result<bool, UsageError> {
+    check_message(msg, ecc)?;
+    let msg = to_systematic(msg);
+    crate::decoder_impl::is_corrupted(&msg, ecc)
+}
+
+/// Corrects `msg` in place and returns the number of errors corrected.
+///
+/// If the message can't be corrected, `msg` is left unchanged.
+pub fn correct(msg: &mut [u8], ecc: u8, erase_pos: Option<&[u8]>) -> Result<usize, CorrectionError> {
+    check_message(msg, ecc)?;
+
+    // Position j of the codeword is the coefficient of x^j of the
+    // systematic codeword, which is at index n - 1 - j.
+    let mut systematic_erase_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+    let erase_pos = match erase_pos {
+        Some(erase_pos) => {
+            if erase_pos.len() > systematic_erase_pos.len() {
+                return Err(CorrectionError::TooManyErrors);
+            }
+            for (i, (x, &pos)) in systematic_erase_pos.iter_mut().zip(erase_pos).enumerate() {
+                if pos as usize >= msg.len() || erase_pos[..i].contains(&pos) {
+                    return Err(invalid_erase_pos().into());
+                }
+                *x = (msg.len() - 1 - pos as usize) as u8;
+            }
+            Some(&systematic_erase_pos[..erase_pos.len()])
+        }
+        None => None,
+    };
+
+    let (corrected, fixed) = correct_err_count(&to_systematic(msg), ecc, erase_pos)?;
+    for (j, x) in msg.iter_mut().enumerate() {
+        *x = gf::div(corrected[corrected.len() - 1 - j], weight(j, corrected.len()));
+    }
+    Ok(fixed)
+}
+
+/// Corrects `msg` and writes the data symbols it encodes to `out`, which must be
+/// exactly `msg.len() - ecc` symbols long. Returns the number of errors corrected.
+pub fn decode(msg: &[u8], ecc: u8, erase_pos: Option<&[u8]>, out: &mut [u8]) -> Result<usize, CorrectionError> {
+    check_message(msg, ecc)?;
+    if out.len() != msg.len() - ecc as usize {
+        return Err(invalid_output_len().into());
+    }
+
+    let mut corrected = Polynom::from(msg);
+    let fixed = correct(&mut corrected, ecc, erase_pos)?;
+    interpolate(&gf::EXP[..out.len()], &corrected[..out.len()], out);
+    Ok(fixed)
+}
+
+fn check_message(msg: &[u8], ecc: u8) -> Result<(), UsageError> {
+    if ecc >= 31 {
+        return Err(invalid_ecc());
+    }
+    if msg.len() > 31 {
+        return Err(invalid_data_len());
+    }
+    if msg.len() < ecc as usize {
+        return Err(crate::err::invalid_data_len_for_ecc());
+    }
+    if msg.iter().any(|&x| x > 31) {
+        return Err(invalid_symbol());
+    }
+    Ok(())
+}
+
+/// `1 / Π (α^j - α^i)` for all positions `i != j` of a message of length `msg_len`
+fn weight(j: usize, msg_len: usize) -> u8 {
+    let mut product = 1;
+    for i in (0..msg_len).filter(|&i| i != j) {
+        product = gf::mul(product, gf::EXP[j] ^ gf::EXP[i]);
+    }
+    gf::inverse(product)
+}
+
+fn to_systematic(msg: &[u8]) -> Polynom {
+    let mut systematic = Polynom::with_length(msg.len());
+    for (j, &x) in msg.iter().enumerate() {
+        systematic[msg.len() - 1 - j] = gf::mul(x, weight(j, msg.len()));
+    }
+    systematic
+}
+
+/// Lagrange interpolation of the polynomial with `points.len()` coefficients
+/// that evaluates to `values[j]` at `points[j]`. The points must be distinct.
+pub(crate) fn interpolate(points: &[u8], values: &[u8], out: &mut [u8]) {
+    // Π (x - x_i) over all of the points
+    let mut all = polynom![1];
+    for &x in points {
+        all = all.mul(&[1, x]);
+    }
+
+    out.iter_mut().for_each(|x| *x = 0);
+    for (&point, &value) in points.iter().zip(values) {
+        if value == 0 {
+            continue;
+        }
+        // Π (x - x_i) over all of the points except x_j, which is 0 everywhere
+        // except at x_j.
+        let (basis, _) = all.div(&[1, point]);
+        let scale = gf::div(value, basis.eval(point));
+        for (x, &b) in out.iter_mut().zip(basis.iter()) {
+            *x ^= gf::mul(b, scale);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_is_evaluation() {
+        let data = [3, 1, 4, 1, 5];
+        let mut out = [0; 9];
+        encode(&data, 4, &mut out).unwrap();
+        for (j, &x) in out.iter().enumerate() {
+            let mut y = 0;
+            for &coef in data.iter() {
+                y = gf::mul(y, gf::pow(2, j as i32)) ^ coef;
+            }
+            assert_eq!(y, x);
+        }
+        assert!(!is_corrupted(&out, 4).unwrap());
+
+        assert!(encode(&data, 4, &mut out[..8]).is_err());
+        assert!(encode(&data, 27, &mut [0; 32]).is_err());
+        assert!(encode(&[32], 4, &mut [0; 5]).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3, 2, 3, 8, 4, 6, 2, 6, 4, 3, 3, 8, 3, 2, 7, 9];
+        for ecc in 0..31u8 {
+            for data_len in 1..=31 - ecc as usize {
+                let data = &data[..data_len];
+                let mut codeword = [0; 31];
+                let codeword = &mut codeword[..data_len + ecc as usize];
+                encode(data, ecc, codeword).unwrap();
+
+                let mut decoded = [0; 31];
+                let decoded = &mut decoded[..data_len];
+                assert_eq!(0, decode(codeword, ecc, None, decoded).unwrap());
+                assert_eq!(data, decoded);
+
+                // Correct as many errors and erasures as possible
+                let errors = ecc as usize / 2;
+                let erasures = ecc as usize % 2;
+                let mut received = [0; 31];
+                let received = &mut received[..codeword.len()];
+                received.copy_from_slice(codeword);
+                for x in received[..errors + erasures].iter_mut() {
+                    *x = (*x + 1) % 32;
+                }
+                let erase_pos = [0];
+                let erase_pos = if erasures == 0 { None } else { Some(&erase_pos[..]) };
+                assert_eq!(erasures == 0 && errors == 0, !is_corrupted(received, ecc).unwrap());
+                // An erasure that already has the right value isn't counted
+                let fixed = decode(received, ecc, erase_pos, decoded).unwrap();
+                assert!(fixed >= errors && fixed <= errors + erasures);
+                assert_eq!(data, decoded);
+                assert_eq!(fixed, correct(received, ecc, erase_pos).unwrap());
+                assert_eq!(codeword, received);
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_errors() {
+        let mut codeword = [0; 8];
+        encode(&[1, 2, 3, 4], 4, &mut codeword).unwrap();
+        let mut received = codeword;
+        received[1] ^= 1;
+        received[2] ^= 1;
+        received[3] ^= 1;
+        let before = received;
+        assert!(correct(&mut received, 4, None).is_err());
+        assert_eq!(before, received);
+
+        assert!(correct(&mut received, 4, Some(&[8])).is_err());
+        assert!(matches!(correct(&mut received, 4, Some(&[1, 1])), Err(CorrectionError::UsageError(_))));
+        assert!(decode(&received, 4, None, &mut [0; 3]).is_err());
+    }
+}
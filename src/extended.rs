@@ -0,0 +1,377 @@
+//! Singly and doubly extended Reed-Solomon codes.
+//!
+//! Regular codewords are limited to 31 symbols. An extended code appends one or
+//! two extra ECC symbols to a regular codeword, which allows codewords of up to
+//! 32 or 33 symbols, and the code can still correct any `ecc` errors and
+//! erasures, with each error counting twice.
+//!
+//! With `ecc` ECC symbols, a codeword is a regular codeword with `ecc - 1`
+//! ([`Extension::Singly`]) or `ecc - 2` ([`Extension::Doubly`]) ECC symbols
+//! followed by the extension symbols. If the regular codeword is the
+//! polynomial `c(x)`, the first extension symbol is `c(α^(ecc - 1))` or
+//! `c(α^(ecc - 2))` respectively, and the second one is `c(α^-1)`. With a
+//! single ECC symbol, the extension symbol is the XOR of all of the others.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::extended::{ExtendedDecoder, ExtendedEncoder, Extension};
+//!
+//! let encoder = ExtendedEncoder::new(6, Extension::Doubly).unwrap();
+//! let decoder = ExtendedDecoder::new(6, Extension::Doubly).unwrap();
+//!
+//! let data = [7; 27];
+//! let mut encoded = encoder.encode(&data).unwrap();
+//! assert_eq!(33, encoded.len());
+//!
+//! // Corrupt both extension symbols and a regular symbol
+//! encoded[0] = 0;
+//! encoded[31] ^= 1;
+//! encoded[32] ^= 1;
+//!
+//! let corrected = decoder.correct(&encoded, None).unwrap();
+//! assert_eq!(&data, corrected.data());
+//! ```
+
+use crate::buffer::Buffer;
+use crate::decoder_impl::Decoder;
+use crate::encoder_impl::encode;
+use crate::err::{invalid_data_len, invalid_data_len_for_ecc, invalid_ecc, invalid_erase_pos, invalid_symbol, CorrectionError, UsageError};
+use crate::gf;
+use crate::gf::poly::Polynom;
+use crate::gf::poly_math::*;
+
+/// Number of extension symbols of an extended code
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Extension {
+    /// One extension symbol, for codewords of up to 32 symbols
+    Singly,
+
+    /// Two extension symbols, for codewords of up to 33 symbols
+    Doubly,
+}
+
+impl Extension {
+    fn symbols(self) -> usize {
+        match self {
+            Extension::Singly => 1,
+            Extension::Doubly => 2,
+        }
+    }
+
+    /// Exponents of the points at which the regular codeword is evaluated to
+    /// get the extension symbols, for a regular codeword with `base_ecc` ECC symbols.
+    fn exponents(self, base_ecc: usize) -> [i32; 2] {
+        [base_ecc as i32, -1]
+    }
+}
+
+fn check_ecc(ecc: u8, extension: Extension) -> Result<(), UsageError> {
+    // NOTE: decoding needs up to `ecc` syndromes at once
+    if (ecc as usize) < extension.symbols() || ecc >= 31 {
+        return Err(invalid_ecc());
+    }
+    Ok(())
+}
+
+/// Evaluates the regular codeword `msg` at α^`exponent`
+fn eval_at(msg: &[u8], exponent: i32) -> u8 {
+    if msg.is_empty() {
+        0
+    } else {
+        msg.eval(gf::pow(2, exponent))
+    }
+}
+
+/// Encoder for extended codes
+#[derive(Debug, Copy, Clone)]
+pub struct ExtendedEncoder {
+    ecc: u8,
+    extension: Extension,
+}
+
+impl ExtendedEncoder {
+    /// Create encoder for codewords with `ecc` ECC symbols, including the extension symbols.
+    ///
+    /// `ecc` must be at least the number of extension symbols and less than 31.
+    pub fn new(ecc: u8, extension: Extension) -> Result<ExtendedEncoder, UsageError> {
+        check_ecc(ecc, extension)?;
+        Ok(ExtendedEncoder { ecc, extension })
+    }
+
+    /// Encodes passed `&[u8]` slice and returns `Buffer` with result.
+    ///
+    /// The codeword can be up to 31 symbols long plus the number of extension symbols.
+    pub fn encode(&self, data: &[u8]) -> Result<Buffer, UsageError> {
+        let base_ecc = self.ecc as usize - self.extension.symbols();
+        let base = encode(data, base_ecc as u8)?;
+
+        let mut msg = Polynom::from(&base[..]);
+        for &exponent in self.extension.exponents(base_ecc)[..self.extension.symbols()].iter() {
+            msg.push(eval_at(&base, exponent));
+        }
+        Ok(Buffer::from_polynom(msg, data.len()))
+    }
+}
+
+/// Decoder for extended codes
+#[derive(Debug, Copy, Clone)]
+pub struct ExtendedDecoder {
+    ecc: u8,
+    extension: Extension,
+}
+
+impl ExtendedDecoder {
+    /// Create decoder for codewords with `ecc` ECC symbols, including the extension symbols.
+    ///
+    /// `ecc` must be at least the number of extension symbols and less than 31.
+    pub fn new(ecc: u8, extension: Extension) -> Result<ExtendedDecoder, UsageError> {
+        check_ecc(ecc, extension)?;
+        Ok(ExtendedDecoder { ecc, extension })
+    }
+
+    /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
+    /// Also includes the number of errors corrected.
+    ///
+    /// Erasure positions may include the extension symbols.
+    pub fn correct_err_count(&self,
+                             msg: &[u8],
+                             erase_pos: Option<&[u8]>)
+                             -> Result<(Buffer, usize), CorrectionError> {
+        self.check_message(msg)?;
+        let erase_pos = erase_pos.unwrap_or(&[]);
+        if erase_pos.len() > self.ecc as usize {
+            return Err(CorrectionError::TooManyErrors);
+        }
+        if erase_pos.iter().any(|&pos| pos as usize >= msg.len()) {
+            return Err(invalid_erase_pos().into());
+        }
+        if erase_pos.iter().enumerate().any(|(i, pos)| erase_pos[..i].contains(pos)) {
+            return Err(invalid_erase_pos().into());
+        }
+
+        let ext = self.extension.symbols();
+        let base_len = msg.len() - ext;
+        let base_ecc = self.ecc as usize - ext;
+        let exponents = self.extension.exponents(base_ecc);
+
+        let mut base_erase_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        let mut base_erase_count = 0;
+        let mut ext_erased = [false; 2];
+        for &pos in erase_pos {
+            if (pos as usize) < base_len {
+                base_erase_pos[base_erase_count] = pos;
+                base_erase_count += 1;
+            } else {
+                ext_erased[pos as usize - base_len] = true;
+            }
+        }
+        let base_erase_pos = &base_erase_pos[..base_erase_count];
+
+        // It isn't known up front whether the extension symbols are correct,
+        // so each combination of trusting them or treating them as erased is
+        // tried. Of the codewords found, the closest one to the message is the
+        // right one as long as the message can be corrected at all.
+        let mut best: Option<(Polynom, usize)> = None;
+        for trusted in 0..1usize << ext {
+            let is_trusted = |i: usize| trusted & (1 << i) != 0;
+            if (0..ext).any(|i| ext_erased[i] && is_trusted(i)) {
+                continue;
+            }
+
+            // The syndromes that can be used are c(α^0)..c(α^(base_ecc - 1)),
+            // which are 0 for a valid codeword, extended by the points of the
+            // trusted extension symbols.
+            let low = if ext == 2 && is_trusted(1) { -1 } else { 0 };
+            let high = if is_trusted(0) { base_ecc as i32 } else { base_ecc as i32 - 1 };
+            let synd_len = (high - low + 1) as usize;
+            if base_erase_count > synd_len {
+                continue;
+            }
+
+            let mut synd = Polynom::with_length(synd_len + 1);
+            for (k, exponent) in (low..=high).enumerate() {
+                let mut s = eval_at(&msg[..base_len], exponent);
+                for i in (0..ext).filter(|&i| is_trusted(i) && exponents[i] == exponent) {
+                    s ^= msg[base_len + i];
+                }
+                synd[k + 1] = s;
+            }
+
+            let mut corrected = Polynom::from(msg);
+            if synd.iter().any(|&x| x != 0) {
+                // Starting at c(α^-1) is the same as starting at c'(α^0), where
+                // c' has the symbol for x^d scaled by α^-d.
+                let errata = Decoder::new(synd_len as u8).errata(&synd, base_erase_pos, base_len);
+                let (err_pos, magnitudes) = match errata {
+                    Ok(errata) => errata,
+                    Err(_) => continue,
+                };
+                for (&pos, &magnitude) in err_pos.iter().zip(magnitudes.iter()) {
+                    let pos = pos as usize;
+                    corrected[pos] ^= if low == -1 {
+                        gf::mul(magnitude, gf::pow(2, (base_len - 1 - pos) as i32))
+                    } else {
+                        magnitude
+                    };
+                }
+            }
+            for i in (0..ext).filter(|&i| !is_trusted(i)) {
+                corrected[base_len + i] = eval_at(&corrected[..base_len], exponents[i]);
+            }
+            if !self.is_codeword(&corrected) {
+                continue;
+            }
+
+            let distance = (0..msg.len())
+                .filter(|&pos| corrected[pos] != msg[pos] && !erase_pos.contains(&(pos as u8)))
+                .count();
+            let is_better = match best {
+                None => true,
+                Some((_, best_distance)) => distance < best_distance,
+            };
+            if is_better {
+                best = Some((corrected, distance));
+            }
+        }
+
+        let (corrected, _) = best.ok_or(CorrectionError::TooManyErrors)?;
+        let fixed = (0..msg.len())
+            .filter(|&pos| corrected[pos] != msg[pos] || erase_pos.contains(&(pos as u8)))
+            .count();
+        Ok((Buffer::from_polynom(corrected, msg.len() - self.ecc as usize), fixed))
+    }
+
+    /// Decodes block-encoded message and returns `Buffer` with corrected message and ecc offset.
+    ///
+    /// Erasure positions may include the extension symbols.
+    pub fn correct(&self,
+                   msg: &[u8],
+                   erase_pos: Option<&[u8]>)
+                   -> Result<Buffer, CorrectionError> {
+        self.correct_err_count(msg, erase_pos).map(|(r, _)| r)
+    }
+
+    /// Checks if the message is corrupted.
+    pub fn is_corrupted(&self, msg: &[u8]) -> Result<bool, UsageError> {
+        self.check_message(msg)?;
+        Ok(!self.is_codeword(msg))
+    }
+
+    fn check_message(&self, msg: &[u8]) -> Result<(), UsageError> {
+        if msg.len() > crate::POLYNOMIAL_MAX_LENGTH + self.extension.symbols() {
+            return Err(invalid_data_len());
+        }
+        if msg.len() < self.ecc as usize {
+            return Err(invalid_data_len_for_ecc());
+        }
+        if msg.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        Ok(())
+    }
+
+    fn is_codeword(&self, msg: &[u8]) -> bool {
+        let ext = self.extension.symbols();
+        let base_len = msg.len() - ext;
+        let base_ecc = self.ecc as usize - ext;
+        let base = &msg[..base_len];
+
+        (0..base_ecc as i32).all(|exponent| eval_at(base, exponent) == 0)
+            && self.extension.exponents(base_ecc)[..ext]
+                .iter()
+                .zip(&msg[base_len..])
+                .all(|(&exponent, &x)| eval_at(base, exponent) == x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overall_parity() {
+        let encoder = ExtendedEncoder::new(1, Extension::Singly).unwrap();
+        let encoded = encoder.encode(&[1, 2, 4, 8, 16, 3]).unwrap();
+        assert_eq!(&[1, 2, 4, 8, 16, 3, 28], &encoded[..]);
+        assert_eq!(&[28], encoded.ecc());
+    }
+
+    #[test]
+    fn lengths() {
+        let encoder = ExtendedEncoder::new(4, Extension::Singly).unwrap();
+        assert_eq!(32, encoder.encode(&[1; 28]).unwrap().len());
+        assert!(encoder.encode(&[1; 29]).is_err());
+
+        let encoder = ExtendedEncoder::new(4, Extension::Doubly).unwrap();
+        assert_eq!(33, encoder.encode(&[1; 29]).unwrap().len());
+        assert!(encoder.encode(&[1; 30]).is_err());
+
+        assert!(ExtendedEncoder::new(0, Extension::Singly).is_err());
+        assert!(ExtendedEncoder::new(1, Extension::Doubly).is_err());
+        assert!(ExtendedDecoder::new(31, Extension::Doubly).is_err());
+        assert!(ExtendedDecoder::new(30, Extension::Doubly).is_ok());
+
+        let decoder = ExtendedDecoder::new(4, Extension::Doubly).unwrap();
+        assert!(decoder.is_corrupted(&[0; 34]).is_err());
+        assert!(decoder.is_corrupted(&[0; 3]).is_err());
+        assert!(decoder.is_corrupted(&[32; 8]).is_err());
+        assert!(decoder.correct(&[0; 8], Some(&[8])).is_err());
+    }
+
+    #[test]
+    fn correct() {
+        let data = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5, 8, 9, 7, 9, 3, 2, 3, 8, 4, 6, 2, 6, 4, 3, 3, 8, 3, 2, 7, 9];
+        for &extension in [Extension::Singly, Extension::Doubly].iter() {
+            let ext = extension.symbols();
+            for ecc in ext as u8..=12 {
+                let encoder = ExtendedEncoder::new(ecc, extension).unwrap();
+                let decoder = ExtendedDecoder::new(ecc, extension).unwrap();
+                let data_len = 31 + ext - ecc as usize;
+                let encoded = encoder.encode(&data[..data_len]).unwrap();
+                assert!(!decoder.is_corrupted(&encoded).unwrap());
+
+                for erasures in 0..=ecc as usize {
+                    let errors = (ecc as usize - erasures) / 2;
+                    // Spread the errata over the message, always hitting the last
+                    // symbols, which are the extension symbols.
+                    for offset in 0..3 {
+                        let mut received = encoded;
+                        let mut erase_pos = [0; 30];
+                        for k in 0..errors + erasures {
+                            let pos = (received.len() * 4 - 1 - k * 7 - offset) % received.len();
+                            received[pos] = (received[pos] + 1 + k as u8) % 32;
+                            if let Some(x) = erase_pos[..erasures].get_mut(k) {
+                                *x = pos as u8;
+                            }
+                        }
+                        let erase_pos = if erasures == 0 { None } else { Some(&erase_pos[..erasures]) };
+
+                        let (corrected, fixed) = decoder.correct_err_count(&received, erase_pos).unwrap();
+                        assert_eq!(&encoded[..], &corrected[..]);
+                        assert_eq!(&data[..data_len], corrected.data());
+                        assert_eq!(errors + erasures, fixed);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_errors() {
+        let decoder = ExtendedDecoder::new(4, Extension::Doubly).unwrap();
+        let encoded = ExtendedEncoder::new(4, Extension::Doubly).unwrap().encode(&[1, 2, 3, 4, 5]).unwrap();
+        let mut received = encoded;
+        received[0] ^= 1;
+        received[4] ^= 1;
+        received[8] ^= 1;
+        assert!(decoder.is_corrupted(&received).unwrap());
+        if let Ok(corrected) = decoder.correct(&received, None) {
+            // Miscorrected to a different codeword
+            assert!(!decoder.is_corrupted(&corrected).unwrap());
+            assert_ne!(&encoded[..], &corrected[..]);
+        }
+
+        assert!(matches!(decoder.correct(&received, Some(&[0, 0])), Err(CorrectionError::UsageError(_))));
+        assert!(matches!(decoder.correct(&received, Some(&[8, 8])), Err(CorrectionError::UsageError(_))));
+    }
+}
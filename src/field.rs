@@ -0,0 +1,402 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use crate::err::{invalid_symbol, UsageError};
+use crate::gf;
+use crate::Symbol;
+
+/// An element of GF(2^5), the field that all of the symbols belong to
+///
+/// The field is generated by the primitive polynomial `x^5 + x^2 + 1`, and its
+/// primitive element `α` is 2. Addition and subtraction are both XOR, while
+/// multiplication and division use the same tables as the encoder and decoder.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::Gf32;
+///
+/// let x = Gf32::new(7).unwrap();
+/// let y = Gf32::ALPHA.pow(12);
+///
+/// assert_eq!(Gf32::ZERO, x - x);
+/// assert_eq!(x, x * y / y);
+/// assert_eq!(Some(12), y.log());
+/// assert_eq!(Gf32::ONE, x * x.inv().unwrap());
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Gf32(u8);
+
+impl Gf32 {
+    /// Additive identity
+    pub const ZERO: Gf32 = Gf32(0);
+
+    /// Multiplicative identity
+    pub const ONE: Gf32 = Gf32(1);
+
+    /// Primitive element `α` that the code is built on, which is 2
+    pub const ALPHA: Gf32 = Gf32(2);
+
+    /// Create element from `x` if it is in the range [0, 31]
+    pub const fn new(x: u8) -> Option<Gf32> {
+        if x > 31 {
+            None
+        } else {
+            Some(Gf32(x))
+        }
+    }
+
+    /// Value of the element
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+
+    /// All 32 elements of the field, in increasing order of their values
+    pub fn elements() -> impl Iterator<Item = Gf32> {
+        (0..32).map(Gf32)
+    }
+
+    /// `α^i`. Negative powers are allowed.
+    pub fn exp(i: i32) -> Gf32 {
+        Gf32(gf::EXP[i.rem_euclid(31) as usize])
+    }
+
+    /// The `i` for which `α^i` is `self`, in the range [0, 30], or `None` for 0.
+    pub fn log(self) -> Option<u8> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(gf::LOG[self.0 as usize])
+        }
+    }
+
+    /// Raises the element to the power `i`. Negative powers are allowed.
+    ///
+    /// # Panics
+    /// If the element is 0 and `i` is negative.
+    pub fn pow(self, i: i32) -> Gf32 {
+        if self.0 != 0 {
+            Gf32(gf::pow(self.0, i))
+        } else if i < 0 {
+            panic!("Division by zero")
+        } else if i == 0 {
+            Gf32::ONE
+        } else {
+            Gf32::ZERO
+        }
+    }
+
+    /// Multiplicative inverse, or `None` for 0
+    pub fn inv(self) -> Option<Gf32> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(Gf32(gf::inverse(self.0)))
+        }
+    }
+
+    /// Smallest `n > 0` for which the element raised to the power `n` is 1,
+    /// or `None` for 0
+    pub fn order(self) -> Option<u8> {
+        // The multiplicative group is cyclic of order 31, which is prime, so
+        // every element other than 0 and 1 generates all of it.
+        self.log().map(|log| if log == 0 { 1 } else { 31 })
+    }
+
+    /// Returns `true` if the powers of the element are all of the elements
+    /// other than 0.
+    pub fn is_primitive(self) -> bool {
+        self.order() == Some(31)
+    }
+
+    /// All of the primitive elements, in increasing order of their values
+    pub fn primitive_elements() -> impl Iterator<Item = Gf32> {
+        Gf32::elements().filter(|x| x.is_primitive())
+    }
+}
+
+impl TryFrom<u8> for Gf32 {
+    type Error = UsageError;
+
+    fn try_from(x: u8) -> Result<Self, Self::Error> {
+        Gf32::new(x).ok_or_else(invalid_symbol)
+    }
+}
+
+impl From<Gf32> for u8 {
+    fn from(x: Gf32) -> u8 {
+        x.0
+    }
+}
+
+impl From<Symbol> for Gf32 {
+    fn from(symbol: Symbol) -> Gf32 {
+        Gf32(symbol.value())
+    }
+}
+
+impl From<Gf32> for Symbol {
+    fn from(x: Gf32) -> Symbol {
+        Symbol::new(x.0).unwrap()
+    }
+}
+
+impl fmt::Display for Gf32 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl Neg for Gf32 {
+    type Output = Gf32;
+
+    fn neg(self) -> Gf32 {
+        // Every element is its own additive inverse
+        self
+    }
+}
+
+impl Neg for &Gf32 {
+    type Output = Gf32;
+
+    fn neg(self) -> Gf32 {
+        *self
+    }
+}
+
+fn div(x: u8, y: u8) -> u8 {
+    assert!(y != 0, "Division by zero");
+    gf::div(x, y)
+}
+
+// Implements an operator for all combinations of values and references,
+// along with its assignment variant.
+macro_rules! impl_op {
+    ($op:ident, $method:ident, $op_assign:ident, $method_assign:ident, $f:path) => {
+        impl $op for Gf32 {
+            type Output = Gf32;
+
+            #[inline]
+            fn $method(self, rhs: Gf32) -> Gf32 {
+                Gf32($f(self.0, rhs.0))
+            }
+        }
+
+        impl<'a> $op<&'a Gf32> for Gf32 {
+            type Output = Gf32;
+
+            #[inline]
+            fn $method(self, rhs: &'a Gf32) -> Gf32 {
+                Gf32($f(self.0, rhs.0))
+            }
+        }
+
+        impl<'a> $op<Gf32> for &'a Gf32 {
+            type Output = Gf32;
+
+            #[inline]
+            fn $method(self, rhs: Gf32) -> Gf32 {
+                Gf32($f(self.0, rhs.0))
+            }
+        }
+
+        impl<'a, 'b> $op<&'b Gf32> for &'a Gf32 {
+            type Output = Gf32;
+
+            #[inline]
+            fn $method(self, rhs: &'b Gf32) -> Gf32 {
+                Gf32($f(self.0, rhs.0))
+            }
+        }
+
+        impl $op_assign for Gf32 {
+            #[inline]
+            fn $method_assign(&mut self, rhs: Gf32) {
+                self.0 = $f(self.0, rhs.0);
+            }
+        }
+
+        impl<'a> $op_assign<&'a Gf32> for Gf32 {
+            #[inline]
+            fn $method_assign(&mut self, rhs: &'a Gf32) {
+                self.0 = $f(self.0, rhs.0);
+            }
+        }
+    };
+}
+
+impl_op!(Add, add, AddAssign, add_assign, gf::add);
+impl_op!(Sub, sub, SubAssign, sub_assign, gf::sub);
+impl_op!(Mul, mul, MulAssign, mul_assign, gf::mul);
+impl_op!(Div, div, DivAssign, div_assign, div);
+
+impl Sum for Gf32 {
+    fn sum<I: Iterator<Item = Gf32>>(iter: I) -> Gf32 {
+        iter.fold(Gf32::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Gf32> for Gf32 {
+    fn sum<I: Iterator<Item = &'a Gf32>>(iter: I) -> Gf32 {
+        iter.fold(Gf32::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl Product for Gf32 {
+    fn product<I: Iterator<Item = Gf32>>(iter: I) -> Gf32 {
+        iter.fold(Gf32::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Gf32> for Gf32 {
+    fn product<I: Iterator<Item = &'a Gf32>>(iter: I) -> Gf32 {
+        iter.fold(Gf32::ONE, |acc, x| acc * x)
+    }
+}
+
+#[cfg(feature = "ff")]
+impl subtle::ConditionallySelectable for Gf32 {
+    fn conditional_select(a: &Gf32, b: &Gf32, choice: subtle::Choice) -> Gf32 {
+        Gf32(u8::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+#[cfg(feature = "ff")]
+impl subtle::ConstantTimeEq for Gf32 {
+    fn ct_eq(&self, other: &Gf32) -> subtle::Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+/// Note that multiplication and division use table lookups, so they are not
+/// constant time.
+#[cfg(feature = "ff")]
+impl ff::Field for Gf32 {
+    const ZERO: Gf32 = Gf32::ZERO;
+    const ONE: Gf32 = Gf32::ONE;
+
+    fn random(mut rng: impl rand_core::RngCore) -> Gf32 {
+        Gf32((rng.next_u32() & 31) as u8)
+    }
+
+    fn square(&self) -> Gf32 {
+        self * self
+    }
+
+    fn double(&self) -> Gf32 {
+        // The characteristic of the field is 2
+        Gf32::ZERO
+    }
+
+    fn invert(&self) -> subtle::CtOption<Gf32> {
+        subtle::CtOption::new(self.inv().unwrap_or(Gf32::ZERO), subtle::Choice::from((self.0 != 0) as u8))
+    }
+
+    fn sqrt_ratio(num: &Gf32, div: &Gf32) -> (subtle::Choice, Gf32) {
+        // Squaring is a bijection in a field of characteristic 2, so every
+        // element has exactly one square root, which is x^16.
+        match div.inv() {
+            Some(inv) => (subtle::Choice::from(1), (num * inv).pow(16)),
+            None => (subtle::Choice::from((num.0 == 0) as u8), Gf32::ZERO),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+    use super::Gf32;
+
+    #[test]
+    fn field() {
+        for x in Gf32::elements() {
+            assert_eq!(Gf32::ZERO, x + x);
+            assert_eq!(x, -x);
+            assert_eq!(x, x * Gf32::ONE);
+            assert_eq!(Gf32::ZERO, x * Gf32::ZERO);
+            for y in Gf32::elements() {
+                assert_eq!(x + y, y + x);
+                assert_eq!(x * y, y * x);
+                assert_eq!(x, x + y - y);
+                if y != Gf32::ZERO {
+                    assert_eq!(x, x * y / y);
+                    assert_eq!(x / y, x * y.inv().unwrap());
+                }
+                for z in Gf32::elements() {
+                    assert_eq!(x * (y + z), x * y + x * z);
+                    assert_eq!((x * y) * z, x * (y * z));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn powers() {
+        let mut x = Gf32::ONE;
+        for i in 0..31 {
+            assert_eq!(x, Gf32::exp(i));
+            assert_eq!(x, Gf32::ALPHA.pow(i));
+            assert_eq!(Some(i as u8), x.log());
+            assert_eq!(x.inv().unwrap(), Gf32::exp(-i));
+            x *= Gf32::ALPHA;
+        }
+        assert_eq!(Gf32::ONE, x);
+        assert_eq!(Gf32::ONE, Gf32::ZERO.pow(0));
+        assert_eq!(Gf32::ZERO, Gf32::ZERO.pow(3));
+        assert_eq!(None, Gf32::ZERO.log());
+        assert_eq!(None, Gf32::ZERO.inv());
+
+        let elements = [Gf32(3), Gf32(9), Gf32(17)];
+        assert_eq!(Gf32(3) + Gf32(9) + Gf32(17), elements.iter().sum());
+        assert_eq!(Gf32(3) * Gf32(9) * Gf32(17), elements.iter().copied().product());
+    }
+
+    #[test]
+    fn primitive() {
+        assert_eq!(30, Gf32::primitive_elements().count());
+        assert!(Gf32::ALPHA.is_primitive());
+        assert!(!Gf32::ONE.is_primitive());
+        assert_eq!(Some(1), Gf32::ONE.order());
+        assert_eq!(None, Gf32::ZERO.order());
+        for x in Gf32::primitive_elements() {
+            let mut seen = [false; 32];
+            for i in 0..31 {
+                seen[x.pow(i).value() as usize] = true;
+            }
+            assert_eq!(31, seen.iter().filter(|&&x| x).count());
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Some(Gf32(31)), Gf32::new(31));
+        assert_eq!(None, Gf32::new(32));
+        assert!(Gf32::try_from(32).is_err());
+        assert_eq!(17, u8::from(Gf32::try_from(17).unwrap()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn divide_by_zero() {
+        let _ = Gf32::ONE / Gf32::ZERO;
+    }
+
+    #[cfg(feature = "ff")]
+    #[test]
+    fn ff() {
+        use ff::Field;
+
+        for x in Gf32::elements() {
+            assert_eq!(x * x, x.square());
+            assert_eq!(x, x.sqrt().unwrap().square());
+            assert_eq!(bool::from(x.is_zero()), bool::from(x.invert().is_none()));
+            for y in Gf32::elements().skip(1) {
+                let (is_square, root) = Gf32::sqrt_ratio(&x, &y);
+                assert!(bool::from(is_square));
+                assert_eq!(x / y, root.square());
+            }
+        }
+        assert!(!bool::from(Gf32::sqrt_ratio(&Gf32::ONE, &Gf32::ZERO).0));
+    }
+}
@@ -16,7 +16,7 @@ pub const LOG: [u8; LOG_SIZE] = [
     0x1e, 0x11, 0x7, 0x16, 0x1c, 0x1a, 0x15, 0x19, 0x9, 0x10, 0xd, 0xe, 0x18, 0xf,
 ];
 
-/// Primitive operations over Galua Fields
+// Primitive operations over Galua Fields
 
 #[allow(dead_code)]
 #[inline]
@@ -25,7 +25,7 @@ impl Polynom {
         if in_array.len() > poly.array.len() {
             // TODO: Make this a regular assert!() once panics in const
             //       functions are allowed: https://rust-lang.github.io/rfcs/2345-const-panic.html
-            #[allow(unconditional_panic)]
+            #[allow(unconditional_panic, clippy::no_effect, clippy::out_of_bounds_indexing)]
             ["in_array must not be bigger than crate::POLYNOMIAL_MAX_LENGTH * 3 / 2"][1000];
         }
         // NOTE: rustc seems to be able to convert this into a memcpy for us - and the
@@ -9,6 +9,7 @@ pub trait Scale {
 
 pub trait Add {
     fn add(&self, rhs: &Self) -> Polynom;
+    #[allow(dead_code)]
     fn add_assign(&mut self, rhs: &Self) -> &mut Self;
 }
 
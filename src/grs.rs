@@ -0,0 +1,528 @@
+//! Generalized Reed-Solomon codes.
+//!
+//! A [`GrsCode`] is defined by a list of distinct evaluation points `x_j` and
+//! a list of multipliers `v_j`. The data symbols are treated as the
+//! coefficients of a polynomial `f(x)`, highest degree first, and symbol `j`
+//! of the codeword is `v_j * f(x_j)`.
+//!
+//! The points can be any of the 32 elements of GF(2^5), including 0, so a
+//! codeword has at most 32 symbols.
+//!
+//! Decoding maps a received message onto a message of the cyclic code used by
+//! [`correct`](crate::correct), so the errors and erasures that can be
+//! corrected are the same: any `ecc` errors and erasures, with each error
+//! counting twice.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::grs::GrsCode;
+//!
+//! let code = GrsCode::new(&[3, 9, 17, 30, 22, 5], &[1, 4, 4, 7, 2, 9], 2).unwrap();
+//!
+//! let mut codeword = [0; 6];
+//! code.encode(&[11, 27], &mut codeword).unwrap();
+//!
+//! // Corrupt one symbol and erase another
+//! codeword[1] ^= 3;
+//! codeword[4] = 0;
+//!
+//! let mut data = [0; 2];
+//! code.decode(&codeword, Some(&[4]), &mut data).unwrap();
+//! assert_eq!([11, 27], data);
+//! ```
+
+use crate::decoder_impl::Decoder;
+use crate::err::{invalid_code, invalid_codeword_len, invalid_combined_len, invalid_ecc, invalid_erase_pos, invalid_output_len, invalid_symbol, CorrectionError, UsageError};
+use crate::evaluation::interpolate;
+use crate::gf;
+use crate::gf::poly::Polynom;
+use crate::gf::poly_math::*;
+use crate::matrix::Matrix;
+
+// All of the nonzero elements and 0
+const MAX_LENGTH: usize = crate::POLYNOMIAL_MAX_LENGTH + 1;
+
+/// A generalized Reed-Solomon code
+#[derive(Debug, Copy, Clone)]
+pub struct GrsCode {
+    points: [u8; MAX_LENGTH],
+    multipliers: [u8; MAX_LENGTH],
+    // Multipliers of the dual code, which are the columns of the parity check matrix
+    dual_multipliers: [u8; MAX_LENGTH],
+    len: usize,
+    data_len: usize,
+}
+
+impl GrsCode {
+    /// Create code with the evaluation points `points`, the column multipliers
+    /// `multipliers` and `data_len` data symbols.
+    ///
+    /// The points must be distinct, the multipliers must be nonzero and there
+    /// must be one point and one multiplier per codeword symbol. One of the
+    /// points may be 0, which allows codewords of up to 32 symbols.
+    pub fn new(points: &[u8], multipliers: &[u8], data_len: usize) -> Result<GrsCode, UsageError> {
+        if points.len() != multipliers.len() || points.len() > MAX_LENGTH || data_len > points.len() {
+            return Err(invalid_code());
+        }
+        if points.len() - data_len >= 31 {
+            return Err(invalid_ecc());
+        }
+        for (i, &x) in points.iter().enumerate() {
+            if x > 31 || points[..i].contains(&x) {
+                return Err(invalid_code());
+            }
+        }
+        if multipliers.iter().any(|&v| v == 0 || v > 31) {
+            return Err(invalid_code());
+        }
+
+        let mut code = GrsCode {
+            points: [0; MAX_LENGTH],
+            multipliers: [0; MAX_LENGTH],
+            dual_multipliers: [0; MAX_LENGTH],
+            len: points.len(),
+            data_len,
+        };
+        code.points[..points.len()].copy_from_slice(points);
+        code.multipliers[..points.len()].copy_from_slice(multipliers);
+        for (j, &v) in multipliers.iter().enumerate() {
+            code.dual_multipliers[j] = gf::inverse(gf::mul(v, code.point_product(j)));
+        }
+        Ok(code)
+    }
+
+    /// Create the code whose codewords are the messages that [`encode`](crate::encode)
+    /// produces for `data_len` data symbols and `ecc` ECC symbols.
+    ///
+    /// The codewords are the same, but [`GrsCode::encode`] maps data to them differently.
+    pub fn reed_solomon(data_len: usize, ecc: u8) -> Result<GrsCode, UsageError> {
+        if ecc >= 31 {
+            return Err(invalid_ecc());
+        }
+        let len = data_len + ecc as usize;
+        if len > crate::POLYNOMIAL_MAX_LENGTH {
+            return Err(invalid_combined_len());
+        }
+
+        // Symbol p is the coefficient of x^(len - 1 - p), and a message is
+        // valid if it evaluates to 0 at α^0..α^(ecc - 1), so the dual code
+        // has the points α^(len - 1 - p) and multipliers of 1.
+        let mut points = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        for (p, x) in points[..len].iter_mut().enumerate() {
+            *x = gf::EXP[len - 1 - p];
+        }
+        let points = &points[..len];
+        let mut multipliers = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        for (p, v) in multipliers[..len].iter_mut().enumerate() {
+            *v = gf::inverse(point_product(points, p));
+        }
+        GrsCode::new(points, &multipliers[..len], data_len)
+    }
+
+    /// Number of symbols in a codeword
+    pub fn codeword_len(&self) -> usize {
+        self.len
+    }
+
+    /// Number of data symbols in a codeword
+    pub fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Number of ECC symbols in a codeword
+    pub fn ecc_len(&self) -> usize {
+        self.len - self.data_len
+    }
+
+    /// Evaluation points
+    pub fn points(&self) -> &[u8] {
+        &self.points[..self.len]
+    }
+
+    /// Column multipliers
+    pub fn multipliers(&self) -> &[u8] {
+        &self.multipliers[..self.len]
+    }
+
+    /// Encodes `data` into `out`, which must be [`codeword_len`](GrsCode::codeword_len)
+    /// symbols long.
+    pub fn encode(&self, data: &[u8], out: &mut [u8]) -> Result<(), UsageError> {
+        if data.len() != self.data_len {
+            return Err(invalid_codeword_len());
+        }
+        if data.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        if out.len() != self.len {
+            return Err(invalid_output_len());
+        }
+
+        for (j, x) in out.iter_mut().enumerate() {
+            let y = if data.is_empty() { 0 } else { data.eval(self.points[j]) };
+            *x = gf::mul(self.multipliers[j], y);
+        }
+        Ok(())
+    }
+
+    /// Parity check matrix of the code.
+    ///
+    /// Row `l` holds `u_j * x_j^l`, where `u_j` are the multipliers of the dual code.
+    /// Multiplying it by a codeword gives all zeros.
+    pub fn parity_check_matrix(&self) -> Matrix {
+        let mut h = Matrix::zero(self.ecc_len(), self.len);
+        for l in 0..self.ecc_len() {
+            for j in 0..self.len {
+                h.set(l, j, gf::mul(self.dual_multipliers[j], point_pow(self.points[j], l)));
+            }
+        }
+        h
+    }
+
+    /// Checks if `msg` is corrupted.
+    pub fn is_corrupted(&self, msg: &[u8]) -> Result<bool, UsageError> {
+        self.check_message(msg)?;
+        Ok(!self.is_codeword(msg))
+    }
+
+    /// Corrects `msg` in place and returns the number of errors corrected.
+    ///
+    /// If the message can't be corrected, `msg` is left unchanged.
+    pub fn correct(&self, msg: &mut [u8], erase_pos: Option<&[u8]>) -> Result<usize, CorrectionError> {
+        self.check_message(msg)?;
+        let erase_pos = erase_pos.unwrap_or(&[]);
+        if erase_pos.len() > self.ecc_len() {
+            return Err(CorrectionError::TooManyErrors);
+        }
+        if erase_pos.iter().any(|&pos| pos as usize >= self.len) {
+            return Err(invalid_erase_pos().into());
+        }
+        if erase_pos.iter().enumerate().any(|(i, pos)| erase_pos[..i].contains(pos)) {
+            return Err(invalid_erase_pos().into());
+        }
+
+        let zero = self.zero_point();
+        let mut cyclic_erase_pos = [0; crate::POLYNOMIAL_MAX_LENGTH];
+        let mut cyclic_erase_count = 0;
+        for &pos in erase_pos {
+            if Some(pos as usize) != zero {
+                cyclic_erase_pos[cyclic_erase_count] = self.cyclic_pos(pos as usize) as u8;
+                cyclic_erase_count += 1;
+            }
+        }
+        let cyclic_erase_pos = &cyclic_erase_pos[..cyclic_erase_count];
+
+        let mut used = [false; crate::POLYNOMIAL_MAX_LENGTH];
+        for j in (0..self.len).filter(|&j| Some(j) != zero) {
+            used[self.cyclic_pos(j)] = true;
+        }
+
+        // The symbol at the point 0 only contributes to the first syndrome, so
+        // it can't be corrected by the cyclic code. It's either trusted, or the
+        // first syndrome is left out and the symbol is computed from the others
+        // afterwards. Of the codewords found, the closest one to the message is
+        // the right one as long as the message can be corrected at all.
+        let zero_erased = matches!(zero, Some(z) if erase_pos.contains(&(z as u8)));
+        let mut best: Option<([u8; MAX_LENGTH], usize)> = None;
+        for &trusted in &[true, false] {
+            if trusted && zero_erased || !trusted && (zero.is_none() || self.ecc_len() == 0) {
+                continue;
+            }
+            let low = if trusted { 0 } else { 1 };
+            let synd_len = self.ecc_len() - low;
+            if cyclic_erase_count > synd_len {
+                continue;
+            }
+
+            let mut cyclic = self.cyclic_message(msg);
+            let mut synd = Polynom::with_length(synd_len + 1);
+            for k in 0..synd_len {
+                synd[k + 1] = cyclic.eval(gf::EXP[k + low]);
+            }
+            if trusted && synd_len > 0 {
+                if let Some(z) = zero {
+                    synd[1] ^= gf::mul(self.dual_multipliers[z], msg[z]);
+                }
+            }
+
+            if synd.iter().any(|&x| x != 0) {
+                // Starting at c(α^1) is the same as starting at c'(α^0), where
+                // c' has the symbol for x^d scaled by α^d.
+                let errata = Decoder::new(synd_len as u8).errata(&synd, cyclic_erase_pos, cyclic.len());
+                let (err_pos, magnitudes) = match errata {
+                    Ok(errata) => errata,
+                    Err(_) => continue,
+                };
+                // An error at a position that doesn't belong to any of the
+                // points means that the message was closer to a codeword of
+                // the cyclic code that isn't part of this code.
+                if err_pos.iter().any(|&pos| !used[pos as usize]) {
+                    continue;
+                }
+                for (&pos, &magnitude) in err_pos.iter().zip(magnitudes.iter()) {
+                    let pos = pos as usize;
+                    cyclic[pos] ^= if low == 1 {
+                        gf::mul(magnitude, gf::pow(2, -((cyclic.len() - 1 - pos) as i32)))
+                    } else {
+                        magnitude
+                    };
+                }
+            }
+
+            let mut corrected = [0; MAX_LENGTH];
+            for (j, x) in corrected[..self.len].iter_mut().enumerate().filter(|&(j, _)| Some(j) != zero) {
+                *x = gf::div(cyclic[self.cyclic_pos(j)], self.dual_multipliers[j]);
+            }
+            if let Some(z) = zero {
+                corrected[z] = if trusted {
+                    msg[z]
+                } else {
+                    // The first row of the parity check matrix holds `u_j`,
+                    // so `u_j * msg[j]` sums to 0 over all of the symbols.
+                    let sum = (0..self.len)
+                        .filter(|&j| j != z)
+                        .fold(0, |acc, j| acc ^ gf::mul(self.dual_multipliers[j], corrected[j]));
+                    gf::div(sum, self.dual_multipliers[z])
+                };
+            }
+            if !self.is_codeword(&corrected[..self.len]) {
+                continue;
+            }
+
+            let distance = (0..self.len)
+                .filter(|&pos| corrected[pos] != msg[pos] && !erase_pos.contains(&(pos as u8)))
+                .count();
+            let is_better = match best {
+                None => true,
+                Some((_, best_distance)) => distance < best_distance,
+            };
+            if is_better {
+                best = Some((corrected, distance));
+            }
+        }
+
+        let (corrected, _) = best.ok_or(CorrectionError::TooManyErrors)?;
+        let fixed = (0..self.len)
+            .filter(|&pos| corrected[pos] != msg[pos] || erase_pos.contains(&(pos as u8)))
+            .count();
+        msg.copy_from_slice(&corrected[..self.len]);
+        Ok(fixed)
+    }
+
+    /// Corrects `msg` and writes the data symbols it encodes to `out`, which must be
+    /// [`data_len`](GrsCode::data_len) symbols long. Returns the number of errors corrected.
+    pub fn decode(&self, msg: &[u8], erase_pos: Option<&[u8]>, out: &mut [u8]) -> Result<usize, CorrectionError> {
+        self.check_message(msg)?;
+        if out.len() != self.data_len {
+            return Err(invalid_output_len().into());
+        }
+
+        let mut corrected = Polynom::from(msg);
+        let fixed = self.correct(&mut corrected, erase_pos)?;
+
+        let mut values = [0; MAX_LENGTH];
+        for (j, y) in values[..self.data_len].iter_mut().enumerate() {
+            *y = gf::div(corrected[j], self.multipliers[j]);
+        }
+        interpolate(&self.points[..self.data_len], &values[..self.data_len], out);
+        Ok(fixed)
+    }
+
+    fn check_message(&self, msg: &[u8]) -> Result<(), UsageError> {
+        if msg.len() != self.len {
+            return Err(invalid_codeword_len());
+        }
+        if msg.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        Ok(())
+    }
+
+    fn point_product(&self, j: usize) -> u8 {
+        point_product(self.points(), j)
+    }
+
+    /// Index of the point 0, if it's one of the points.
+    fn zero_point(&self) -> Option<usize> {
+        self.points().iter().position(|&x| x == 0)
+    }
+
+    fn is_codeword(&self, msg: &[u8]) -> bool {
+        let cyclic = self.cyclic_message(msg);
+        (0..self.ecc_len()).all(|l| {
+            let mut s = cyclic.eval(gf::EXP[l]);
+            if l == 0 {
+                if let Some(z) = self.zero_point() {
+                    s ^= gf::mul(self.dual_multipliers[z], msg[z]);
+                }
+            }
+            s == 0
+        })
+    }
+
+    /// Index of symbol `j` in a message of the cyclic code, where `x_j` is α^i
+    /// and symbol 30 - i is the coefficient of x^i.
+    ///
+    /// `x_j` must not be 0.
+    fn cyclic_pos(&self, j: usize) -> usize {
+        crate::POLYNOMIAL_MAX_LENGTH - 1 - gf::LOG[self.points[j] as usize] as usize
+    }
+
+    /// Places `u_j * msg[j]` at the coefficient of x^i, where `x_j` is α^i, so
+    /// that the syndromes of the cyclic code are the syndromes of this code.
+    ///
+    /// The symbol at the point 0 is left out, since it only contributes to
+    /// the first syndrome.
+    fn cyclic_message(&self, msg: &[u8]) -> Polynom {
+        let mut cyclic = Polynom::with_length(crate::POLYNOMIAL_MAX_LENGTH);
+        for (j, &x) in msg.iter().enumerate().filter(|&(j, _)| self.points[j] != 0) {
+            cyclic[self.cyclic_pos(j)] = gf::mul(self.dual_multipliers[j], x);
+        }
+        cyclic
+    }
+}
+
+/// `x^l`, where `0^0` is 1
+fn point_pow(x: u8, l: usize) -> u8 {
+    if x == 0 && l > 0 {
+        0
+    } else {
+        gf::pow(x, l as i32)
+    }
+}
+
+/// `Π (x_j - x_i)` for all points `i != j`
+fn point_product(points: &[u8], j: usize) -> u8 {
+    let mut product = 1;
+    for (i, &x) in points.iter().enumerate() {
+        if i != j {
+            product = gf::mul(product, points[j] ^ x);
+        }
+    }
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::Matrix;
+
+    fn check_parity(h: &Matrix, codeword: &[u8]) -> bool {
+        (0..h.rows()).all(|l| {
+            h.row(l).iter().zip(codeword).fold(0, |acc, (&a, &b)| acc ^ gf::mul(a, b)) == 0
+        })
+    }
+
+    #[test]
+    fn reed_solomon() {
+        let data = [3, 1, 4, 1, 5, 9, 2, 6];
+        for ecc in 0..=23u8 {
+            let code = GrsCode::reed_solomon(data.len(), ecc).unwrap();
+            let h = code.parity_check_matrix();
+
+            let encoded = crate::encode(&data, ecc).unwrap();
+            assert!(!code.is_corrupted(&encoded).unwrap());
+            assert!(check_parity(&h, &encoded));
+
+            let mut codeword = [0; 31];
+            let codeword = &mut codeword[..code.codeword_len()];
+            code.encode(&data, codeword).unwrap();
+            assert!(!crate::is_corrupted(codeword, ecc).unwrap());
+            assert!(check_parity(&h, codeword));
+        }
+    }
+
+    #[test]
+    fn correct() {
+        let points = [5, 9, 17, 3, 30, 22, 1, 14, 8, 27, 2];
+        let multipliers = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 31];
+        let code = GrsCode::new(&points, &multipliers, 4).unwrap();
+        assert_eq!(7, code.ecc_len());
+
+        let mut codeword = [0; 11];
+        code.encode(&[20, 0, 7, 31], &mut codeword).unwrap();
+        assert!(!code.is_corrupted(&codeword).unwrap());
+        assert!(check_parity(&code.parity_check_matrix(), &codeword));
+
+        // 2 errors and 3 erasures
+        let mut received = codeword;
+        received[0] ^= 1;
+        received[10] ^= 16;
+        received[3] = 0;
+        received[4] = 0;
+        received[5] = 0;
+        assert!(code.is_corrupted(&received).unwrap());
+
+        let mut data = [0; 4];
+        assert_eq!(5, code.decode(&received, Some(&[3, 4, 5]), &mut data).unwrap());
+        assert_eq!([20, 0, 7, 31], data);
+        assert_eq!(5, code.correct(&mut received, Some(&[3, 4, 5])).unwrap());
+        assert_eq!(codeword, received);
+
+        assert!(code.correct(&mut received[..10], None).is_err());
+        assert!(code.correct(&mut received, Some(&[11])).is_err());
+        assert!(matches!(code.correct(&mut received, Some(&[3, 3])), Err(CorrectionError::UsageError(_))));
+        assert!(code.decode(&received, None, &mut data[..3]).is_err());
+    }
+
+    #[test]
+    fn zero_point() {
+        let mut points = [0; 32];
+        let mut multipliers = [0; 32];
+        for (j, (x, v)) in points.iter_mut().zip(multipliers.iter_mut()).enumerate() {
+            *x = (j as u8 * 7 + 3) % 32;
+            *v = j as u8 % 31 + 1;
+        }
+        let code = GrsCode::new(&points, &multipliers, 26).unwrap();
+        assert_eq!(32, code.codeword_len());
+        assert_eq!(0, points[27]);
+
+        let data = [9, 2, 17, 30, 0, 5, 12, 8, 1, 22, 6, 19, 3, 27, 11, 4, 7, 31, 14, 2, 25, 10, 16, 13, 20, 1];
+        let mut codeword = [0; 32];
+        code.encode(&data, &mut codeword).unwrap();
+        assert!(!code.is_corrupted(&codeword).unwrap());
+        assert!(check_parity(&code.parity_check_matrix(), &codeword));
+
+        // 3 errors, with and without one at the point 0
+        for &zero_error in &[false, true] {
+            let mut received = codeword;
+            received[0] ^= 5;
+            received[31] ^= 1;
+            received[if zero_error { 27 } else { 24 }] ^= 9;
+            assert!(code.is_corrupted(&received).unwrap());
+
+            let mut out = [0; 26];
+            assert_eq!(3, code.decode(&received, None, &mut out).unwrap());
+            assert_eq!(data, out);
+            assert_eq!(3, code.correct(&mut received, None).unwrap());
+            assert_eq!(codeword, received);
+        }
+
+        // 2 errors and 2 erasures, one of which is at the point 0
+        let mut received = codeword;
+        received[4] ^= 3;
+        received[12] ^= 30;
+        received[27] = 0;
+        received[30] = 0;
+        assert_eq!(4, code.correct(&mut received, Some(&[27, 30])).unwrap());
+        assert_eq!(codeword, received);
+
+        let mut received = codeword;
+        for x in received[..4].iter_mut() {
+            *x ^= 1;
+        }
+        assert!(code.correct(&mut received, None).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(GrsCode::new(&[1, 2, 3], &[1, 1], 1).is_err());
+        assert!(GrsCode::new(&[1, 2, 2], &[1, 1, 1], 1).is_err());
+        assert!(GrsCode::new(&[0, 1, 32], &[1, 1, 1], 1).is_err());
+        assert!(GrsCode::new(&[0, 1, 2], &[1, 1, 1], 1).is_ok());
+        assert!(GrsCode::new(&[1, 2, 3], &[1, 0, 1], 1).is_err());
+        assert!(GrsCode::new(&[1, 2, 3], &[1, 1, 1], 4).is_err());
+        assert!(GrsCode::new(&[1, 2, 3], &[1, 1, 1], 3).is_ok());
+        assert!(GrsCode::reed_solomon(20, 12).is_err());
+        assert!(GrsCode::reed_solomon(0, 31).is_err());
+    }
+}
@@ -0,0 +1,359 @@
+//! Human readable identifiers that can correct typing mistakes.
+//!
+//! An identifier is made up of data symbols followed by ECC symbols and is
+//! written using an [`Alphabet`], optionally split into groups. When reading
+//! an identifier back, case and separators are ignored and characters that
+//! aren't part of the alphabet (eg, an `0` typed instead of an `O`) are
+//! treated as erasures before the identifier is corrected. If the identifier
+//! can't be corrected that way, characters that the alphabet marks as
+//! [confusable](Alphabet::with_confusables) (eg, an `L` that might have been
+//! an `I`) are treated as erasures as well.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::alphabet::RFC4648;
+//! use reed_solomon_32::ident::Scheme;
+//!
+//! let scheme = Scheme::new(&RFC4648, 10, 5).unwrap().with_groups(5, '-').unwrap();
+//!
+//! let ident = scheme.generate(&[7, 4, 11, 11, 14, 22, 14, 17, 11, 3]).unwrap();
+//! assert_eq!("HELLO-WORLD-XFTAN", ident.as_str());
+//!
+//! // A '1' instead of an 'L', a '0' instead of an 'O' and an 'M' instead of an 'N'
+//! let correction = scheme.correct("he1l0 world xftam").unwrap();
+//! assert_eq!("HELLO-WORLD-XFTAN", correction.ident().as_str());
+//! assert_eq!(&[2, 4, 14], correction.changed());
+//! assert_eq!(16, scheme.char_index(14));
+//! ```
+
+use core::fmt;
+use crate::alphabet::{parse_with, Alphabet};
+use crate::decoder_impl::correct_err_count;
+use crate::encoder_impl::encode;
+use crate::err::{invalid_codeword_len, invalid_combined_len, invalid_data_len, invalid_ecc, invalid_separator, CorrectionError, UsageError};
+
+const MAX_IDENT_LEN: usize = crate::POLYNOMIAL_MAX_LENGTH * 2;
+
+/// Describes how identifiers are generated and read
+#[derive(Debug, Copy, Clone)]
+pub struct Scheme<'a> {
+    alphabet: &'a Alphabet,
+    data_len: usize,
+    ecc: u8,
+    group: usize,
+    separator: char,
+}
+
+impl<'a> Scheme<'a> {
+    /// Create scheme for identifiers with `data_len` data symbols and `ecc`
+    /// error correcting symbols, written using `alphabet`.
+    pub fn new(alphabet: &'a Alphabet, data_len: usize, ecc: u8) -> Result<Self, UsageError> {
+        if ecc >= 31 {
+            return Err(invalid_ecc());
+        }
+        if data_len > 31 {
+            return Err(invalid_data_len());
+        }
+        if data_len + ecc as usize > 31 {
+            return Err(invalid_combined_len());
+        }
+        Ok(Scheme {
+            alphabet,
+            data_len,
+            ecc,
+            group: 0,
+            separator: '-',
+        })
+    }
+
+    /// Split identifiers into groups of `size` characters separated by `separator`.
+    ///
+    /// Returns an error if `separator` isn't ASCII or is part of the alphabet.
+    pub fn with_groups(self, size: usize, separator: char) -> Result<Self, UsageError> {
+        if !separator.is_ascii() || self.alphabet.from_char(separator).is_some() {
+            return Err(invalid_separator());
+        }
+        Ok(Scheme {
+            group: size,
+            separator,
+            ..self
+        })
+    }
+
+    /// Total number of symbols in an identifier
+    pub fn symbol_count(&self) -> usize {
+        self.data_len + self.ecc as usize
+    }
+
+    /// Position in the formatted identifier of the character for symbol `index`
+    pub fn char_index(&self, index: usize) -> usize {
+        index + index.checked_div(self.group).unwrap_or(0)
+    }
+
+    /// Generates the identifier for `data`.
+    pub fn generate(&self, data: &[u8]) -> Result<Ident, UsageError> {
+        if data.len() != self.data_len {
+            return Err(invalid_data_len());
+        }
+        let encoded = encode(data, self.ecc)?;
+        Ok(self.format(&encoded))
+    }
+
+    /// Reads a possibly mistyped identifier and corrects it.
+    ///
+    /// Case is ignored, as are whitespace, `-`, `_` and the group separator.
+    /// Any other character that isn't part of the alphabet is treated as an erasure.
+    /// If the identifier can't be corrected, it is corrected again with the
+    /// confusable characters treated as erasures too, as long as that leaves at
+    /// least 2 ECC symbols to detect the errors that remain.
+    pub fn correct(&self, input: &str) -> Result<Correction, CorrectionError> {
+        let mut normalized = [0u8; MAX_IDENT_LEN];
+        let mut normalized_len = 0;
+        for c in input.chars() {
+            if c.is_whitespace() || c == '-' || c == '_' || c == self.separator {
+                continue;
+            }
+            if normalized_len == self.symbol_count() {
+                return Err(invalid_codeword_len().into());
+            }
+            // Characters which can't be encoded as a single byte can never be part of
+            // the alphabet, so replace them with something else that isn't either.
+            normalized[normalized_len] = if c.is_ascii() { c as u8 } else { 0x7f };
+            normalized_len += 1;
+        }
+        if normalized_len != self.symbol_count() {
+            return Err(invalid_codeword_len().into());
+        }
+
+        // NOTE: all characters are ASCII at this point
+        let normalized = core::str::from_utf8(&normalized[..normalized_len]).unwrap();
+        let parsed = parse_with(self.alphabet, normalized)?;
+
+        let known = parsed.erasures().unwrap_or(&[]);
+        let mut erasures = [0u8; crate::POLYNOMIAL_MAX_LENGTH];
+        erasures[..known.len()].copy_from_slice(known);
+        let mut erasure_count = known.len();
+        for (i, c) in normalized.chars().enumerate() {
+            if self.alphabet.is_confusable(c) && !known.contains(&(i as u8)) {
+                erasures[erasure_count] = i as u8;
+                erasure_count += 1;
+            }
+        }
+
+        // Every erasure makes a miscorrection more likely, so the hints are
+        // only used if the identifier can't be corrected without them. Once
+        // the erasures use up all of the ECC symbols, any input decodes to
+        // some identifier.
+        let corrected = match correct_err_count(parsed.symbols(), self.ecc, parsed.erasures()) {
+            Ok((corrected, _)) => corrected,
+            Err(CorrectionError::TooManyErrors) if erasure_count > known.len() && erasure_count + 2 <= self.ecc as usize => {
+                correct_err_count(parsed.symbols(), self.ecc, Some(&erasures[..erasure_count]))?.0
+            }
+            Err(err) => return Err(err),
+        };
+
+        let mut correction = Correction {
+            ident: self.format(&corrected),
+            data: [0; crate::POLYNOMIAL_MAX_LENGTH],
+            data_len: self.data_len,
+            changed: [0; crate::POLYNOMIAL_MAX_LENGTH],
+            changed_len: 0,
+        };
+        correction.data[..self.data_len].copy_from_slice(corrected.data());
+
+        for (i, (a, b)) in parsed.symbols().iter().zip(corrected.iter()).enumerate() {
+            if a != b || known.contains(&(i as u8)) {
+                correction.changed[correction.changed_len] = i as u8;
+                correction.changed_len += 1;
+            }
+        }
+
+        Ok(correction)
+    }
+
+    fn format(&self, symbols: &[u8]) -> Ident {
+        let mut ident = Ident {
+            text: [0; MAX_IDENT_LEN],
+            len: 0,
+        };
+        for (i, &symbol) in symbols.iter().enumerate() {
+            if self.group != 0 && i != 0 && i % self.group == 0 {
+                ident.push(self.separator);
+            }
+            ident.push(self.alphabet.to_char(symbol));
+        }
+        ident
+    }
+}
+
+/// A formatted identifier
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Ident {
+    text: [u8; MAX_IDENT_LEN],
+    len: usize,
+}
+
+impl Ident {
+    /// Text of the identifier
+    pub fn as_str(&self) -> &str {
+        // NOTE: only ASCII characters are ever pushed
+        core::str::from_utf8(&self.text[..self.len]).unwrap()
+    }
+
+    // NOTE: `c` must be ASCII
+    fn push(&mut self, c: char) {
+        self.text[self.len] = c as u8;
+        self.len += 1;
+    }
+}
+
+impl fmt::Debug for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Result of [`Scheme::correct`]
+#[derive(Debug, Copy, Clone)]
+pub struct Correction {
+    ident: Ident,
+    data: [u8; crate::POLYNOMIAL_MAX_LENGTH],
+    data_len: usize,
+    changed: [u8; crate::POLYNOMIAL_MAX_LENGTH],
+    changed_len: usize,
+}
+
+impl Correction {
+    /// The corrected identifier
+    pub fn ident(&self) -> &Ident {
+        &self.ident
+    }
+
+    /// Data symbols of the corrected identifier
+    pub fn data(&self) -> &[u8] {
+        &self.data[..self.data_len]
+    }
+
+    /// Indexes of the symbols that were changed or that couldn't be read.
+    ///
+    /// Use [`Scheme::char_index`] to find the matching characters in the
+    /// formatted identifier.
+    pub fn changed(&self) -> &[u8] {
+        &self.changed[..self.changed_len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scheme;
+    use crate::alphabet::{CROCKFORD, ZBASE32};
+
+    #[test]
+    fn generate() {
+        let scheme = Scheme::new(&CROCKFORD, 6, 4).unwrap();
+        let ident = scheme.generate(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(10, ident.as_str().len());
+        assert!(ident.as_str().starts_with("123456"));
+
+        let grouped = scheme.with_groups(4, ' ').unwrap().generate(&[1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!(&ident.as_str()[..4], &grouped.as_str()[..4]);
+        assert_eq!(" ", &grouped.as_str()[4..5]);
+        assert_eq!(12, grouped.as_str().len());
+
+        assert!(scheme.generate(&[1, 2, 3]).is_err());
+        assert!(scheme.generate(&[1, 2, 3, 4, 5, 32]).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn correct_unchanged() {
+        let scheme = Scheme::new(&ZBASE32, 8, 4).unwrap().with_groups(4, '-').unwrap();
+        let ident = scheme.generate(&[31, 30, 29, 28, 27, 26, 25, 24]).unwrap();
+
+        let correction = scheme.correct(ident.as_str()).unwrap();
+        assert_eq!(ident, *correction.ident());
+        assert_eq!(&[31, 30, 29, 28, 27, 26, 25, 24], correction.data());
+        assert!(correction.changed().is_empty());
+
+        let upper = ident.as_str().to_ascii_uppercase().replace('-', " ");
+        assert_eq!(ident, *scheme.correct(&upper).unwrap().ident());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn correct_typos() {
+        let scheme = Scheme::new(&CROCKFORD, 8, 4).unwrap();
+        let ident = scheme.generate(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+
+        // 'U' isn't part of the Crockford alphabet and '9' is a plain error
+        let mut typed = ident.as_str().as_bytes().to_vec();
+        typed[1] = b'U';
+        typed[5] = b'9';
+        let typed = String::from_utf8(typed).unwrap();
+
+        let correction = scheme.correct(&typed).unwrap();
+        assert_eq!(ident, *correction.ident());
+        assert_eq!(&[1, 5], correction.changed());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn correct_confusables() {
+        let scheme = Scheme::new(&CROCKFORD, 8, 4).unwrap();
+        let ident = scheme.generate(&[25, 11, 3, 4, 7, 9, 10, 12]).unwrap();
+        assert_eq!("SB3479ACTTT7", ident.as_str());
+
+        // Three errors are too many for 4 ECC symbols, unless the '5' and
+        // the '8' are treated as erasures
+        let correction = scheme.correct("583479ADTTT7").unwrap();
+        assert_eq!(ident, *correction.ident());
+        assert_eq!(&[0, 1, 7], correction.changed());
+
+        // Two plain errors are corrected without the hints
+        let correction = scheme.correct("SB3479AC5TD7").unwrap();
+        assert_eq!(ident, *correction.ident());
+        assert_eq!(&[8, 10], correction.changed());
+
+        // Three hints would leave too few ECC symbols to detect errors
+        assert!(scheme.correct("5B3479A85TT7").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn single_typo() {
+        let scheme = Scheme::new(&CROCKFORD, 8, 4).unwrap();
+        let ident = scheme.generate(&[0, 28, 4, 5, 10, 31, 13, 29]).unwrap();
+        assert_eq!("0W45AZDX7VGM", ident.as_str());
+        assert_eq!(ident, *scheme.correct("1W45AZDX7VGM").unwrap().ident());
+
+        // A single typo never results in a different identifier
+        for i in 0..ident.as_str().len() {
+            for c in "0123456789ABCDEFGHJKMNPQRSTVWXYZOILU".chars() {
+                let mut typo = ident.as_str().to_string();
+                typo.replace_range(i..i + 1, &c.to_string());
+                assert_eq!(ident, *scheme.correct(&typo).unwrap().ident(), "{}", typo);
+            }
+        }
+    }
+
+    #[test]
+    fn groups_separator() {
+        let scheme = Scheme::new(&CROCKFORD, 8, 4).unwrap();
+        assert!(scheme.with_groups(4, '-').is_ok());
+        assert!(scheme.with_groups(4, 'A').is_err());
+        assert!(scheme.with_groups(4, '\u{2014}').is_err());
+    }
+
+    #[test]
+    fn correct_wrong_length() {
+        let scheme = Scheme::new(&CROCKFORD, 8, 4).unwrap();
+        assert!(scheme.correct("12345678901").is_err());
+        assert!(scheme.correct("1234567890123").is_err());
+    }
+}
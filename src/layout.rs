@@ -0,0 +1,199 @@
+use crate::err::{invalid_layout, UsageError};
+
+/// Describes where the ECC symbols of a message are placed
+///
+/// Encoding and decoding always work on the logical message, which is the
+/// data followed by the ECC symbols. A `Layout` maps the positions of that
+/// logical message to the positions of the message as it is stored or sent.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::Layout;
+/// use reed_solomon_32::encoder::ENCODER_4;
+/// use reed_solomon_32::decoder::DECODER_4;
+///
+/// let layout = Layout::prefix();
+///
+/// let mut msg = [0; 8];
+/// ENCODER_4.encode_with_layout(&[1, 2, 3, 4], &layout, &mut msg).unwrap();
+/// assert_eq!(&[1, 2, 3, 4], &msg[4..]);
+///
+/// msg[5] = 0;
+/// assert_eq!(1, DECODER_4.correct_with_layout(&mut msg, &layout, None).unwrap());
+/// assert_eq!(&[1, 2, 3, 4], &msg[4..]);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Layout {
+    placement: Placement,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Placement {
+    Suffix,
+    Prefix,
+    Positions {
+        positions: [u8; crate::POLYNOMIAL_MAX_LENGTH - 1],
+        len: u8,
+    },
+}
+
+impl Layout {
+    /// ECC symbols follow the data. This is the layout used by [`encode`](crate::encode).
+    pub const fn suffix() -> Layout {
+        Layout { placement: Placement::Suffix }
+    }
+
+    /// ECC symbols come before the data.
+    pub const fn prefix() -> Layout {
+        Layout { placement: Placement::Prefix }
+    }
+
+    /// ECC symbol `i` is placed at `positions[i]` and the data symbols fill the
+    /// remaining positions in order.
+    ///
+    /// Returns an error if any of the positions appears twice or is greater than 30.
+    /// The number of positions must match the number of ECC symbols of the
+    /// encoder or decoder the layout is used with.
+    pub fn with_positions(positions: &[u8]) -> Result<Layout, UsageError> {
+        let mut layout = [0; crate::POLYNOMIAL_MAX_LENGTH - 1];
+        if positions.len() > layout.len() {
+            return Err(invalid_layout());
+        }
+        for (i, &pos) in positions.iter().enumerate() {
+            if pos as usize >= crate::POLYNOMIAL_MAX_LENGTH || positions[..i].contains(&pos) {
+                return Err(invalid_layout());
+            }
+            layout[i] = pos;
+        }
+        Ok(Layout {
+            placement: Placement::Positions {
+                positions: layout,
+                len: positions.len() as u8,
+            },
+        })
+    }
+
+    /// Physical position of the symbol at `index` of the logical message,
+    /// for a message with `data_len` data symbols and `ecc_len` ECC symbols.
+    ///
+    /// Returns an error if `index` is outside of the message or if the layout
+    /// doesn't fit the message.
+    pub fn physical_index(&self, index: usize, data_len: usize, ecc_len: usize) -> Result<usize, UsageError> {
+        self.check(data_len, ecc_len)?;
+        if index >= data_len + ecc_len {
+            return Err(invalid_layout());
+        }
+        Ok(self.physical_index_unchecked(index, data_len, ecc_len))
+    }
+
+    /// Position in the logical message of the symbol at the physical position
+    /// `index`, for a message with `data_len` data symbols and `ecc_len` ECC symbols.
+    ///
+    /// Returns an error if `index` is outside of the message or if the layout
+    /// doesn't fit the message.
+    pub fn logical_index(&self, index: usize, data_len: usize, ecc_len: usize) -> Result<usize, UsageError> {
+        self.check(data_len, ecc_len)?;
+        if index >= data_len + ecc_len {
+            return Err(invalid_layout());
+        }
+        Ok(self.logical_index_unchecked(index, data_len, ecc_len))
+    }
+
+    pub(crate) fn check(&self, data_len: usize, ecc_len: usize) -> Result<(), UsageError> {
+        if let Placement::Positions { positions, len } = &self.placement {
+            let msg_len = data_len + ecc_len;
+            if *len as usize != ecc_len || positions[..ecc_len].iter().any(|&pos| pos as usize >= msg_len) {
+                return Err(invalid_layout());
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn physical_index_unchecked(&self, index: usize, data_len: usize, ecc_len: usize) -> usize {
+        match &self.placement {
+            Placement::Suffix => index,
+            Placement::Prefix if index < data_len => index + ecc_len,
+            Placement::Prefix => index - data_len,
+            Placement::Positions { positions, .. } => {
+                if index >= data_len {
+                    return positions[index - data_len] as usize;
+                }
+                // The data symbol at `index` is at the `index`th position
+                // that doesn't hold an ECC symbol.
+                let positions = &positions[..ecc_len];
+                (0..data_len + ecc_len)
+                    .filter(|pos| !positions.contains(&(*pos as u8)))
+                    .nth(index)
+                    .unwrap()
+            }
+        }
+    }
+
+    pub(crate) fn logical_index_unchecked(&self, index: usize, data_len: usize, ecc_len: usize) -> usize {
+        match &self.placement {
+            Placement::Suffix => index,
+            Placement::Prefix if index < ecc_len => index + data_len,
+            Placement::Prefix => index - ecc_len,
+            Placement::Positions { positions, .. } => {
+                let positions = &positions[..ecc_len];
+                match positions.iter().position(|&pos| pos as usize == index) {
+                    Some(i) => data_len + i,
+                    None => index - positions.iter().filter(|&&pos| (pos as usize) < index).count(),
+                }
+            }
+        }
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Layout {
+        Layout::suffix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Layout;
+
+    #[test]
+    fn round_trip() {
+        let layouts = [
+            Layout::suffix(),
+            Layout::prefix(),
+            Layout::with_positions(&[0, 3, 9, 4]).unwrap(),
+        ];
+        for layout in layouts.iter() {
+            let mut seen = [false; 10];
+            for index in 0..10 {
+                let physical = layout.physical_index(index, 6, 4).unwrap();
+                assert!(!seen[physical]);
+                seen[physical] = true;
+                assert_eq!(index, layout.logical_index(physical, 6, 4).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn positions() {
+        let layout = Layout::with_positions(&[0, 3, 9, 4]).unwrap();
+        let mut physical = [0; 10];
+        for (i, x) in physical.iter_mut().enumerate() {
+            *x = layout.physical_index(i, 6, 4).unwrap();
+        }
+        assert_eq!([1, 2, 5, 6, 7, 8, 0, 3, 9, 4], physical);
+
+        assert!(layout.physical_index(0, 6, 3).is_err());
+        assert!(layout.physical_index(0, 5, 4).is_err());
+        assert!(layout.physical_index(10, 6, 4).is_err());
+        assert!(Layout::with_positions(&[1, 2, 1]).is_err());
+        assert!(Layout::with_positions(&[31]).is_err());
+    }
+
+    #[test]
+    fn prefix() {
+        let layout = Layout::prefix();
+        assert_eq!(4, layout.physical_index(0, 6, 4).unwrap());
+        assert_eq!(0, layout.physical_index(6, 6, 4).unwrap());
+        assert_eq!(9, layout.logical_index(3, 6, 4).unwrap());
+    }
+}
@@ -42,6 +42,11 @@
 //! ```
 
 #![cfg_attr(not(feature = "std"), no_std)]
+// `usize::div_ceil` and `Option::is_some_and` need a newer compiler than the rest of the crate
+#![allow(clippy::manual_div_ceil, clippy::unnecessary_map_or)]
+
+#[cfg(feature = "ff")]
+extern crate ff_crate as ff;
 
 const POLYNOMIAL_MAX_LENGTH: usize = 31;
 
@@ -51,12 +56,40 @@ mod gf;
 mod encoder_impl;
 mod decoder_impl;
 mod buffer;
+mod codeword;
 mod err;
+mod field;
+mod layout;
+mod polynomial;
+mod symbol;
 
-pub use encoder_impl::encode;
-pub use decoder_impl::{correct, correct_err_count, is_corrupted};
-pub use err::{UsageError, CorrectionError, UsageErrorMessage};
+pub use encoder_impl::{encode, encode_symbols};
+pub use decoder_impl::{
+    correct,
+    correct_err_count,
+    correct_err_count_symbols,
+    correct_symbols,
+    is_corrupted,
+    is_corrupted_symbols,
+};
+pub use err::{UsageError, CorrectionError, LocateError, UsageErrorMessage};
 pub use buffer::Buffer;
+pub use codeword::Codeword;
+pub use field::Gf32;
+pub use layout::Layout;
+pub use polynomial::Poly;
+pub use symbol::Symbol;
+
+pub mod alphabet;
+pub mod analysis;
+pub mod design;
+pub mod evaluation;
+pub mod extended;
+pub mod grs;
+pub mod ident;
+pub mod matrix;
+pub mod pack;
+pub mod stages;
 
 pub mod encoder {
     //! This is a specialized module and generally the [`encode`](crate::encode)
@@ -73,6 +106,8 @@ pub mod encoder {
     //! advantage of this, however.
     pub use crate::encoder_impl::{
         Encoder,
+        EncoderState,
+        Parity,
         ENCODER_0,
         ENCODER_1,
         ENCODER_2,
@@ -117,6 +152,8 @@ pub mod decoder {
     //! help decrease binary size. However, currently they do not significantly do so.
     pub use crate::decoder_impl::{
         Decoder,
+        ErrorLocations,
+        SyndromeState,
         DECODER_0,
         DECODER_1,
         DECODER_2,
@@ -0,0 +1,555 @@
+//! Matrices over GF(2^5).
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::matrix::Matrix;
+//!
+//! // Recover two symbols from any two of three combinations of them
+//! let a = Matrix::vandermonde(&[1, 2, 3], 2).unwrap();
+//! let x = Matrix::from_rows(&[&[9], &[17]]).unwrap();
+//! let b = a.mul(&x).unwrap();
+//!
+//! let a = Matrix::from_rows(&[a.row(0), a.row(2)]).unwrap();
+//! let b = Matrix::from_rows(&[b.row(0), b.row(2)]).unwrap();
+//! assert_eq!(Some(x), a.solve(&b));
+//! ```
+
+use core::fmt;
+use crate::err::{invalid_dimensions, invalid_points, invalid_symbol, UsageError};
+use crate::gf;
+
+const MAX_DIM: usize = 32;
+
+/// A matrix over GF(2^5) with at most 32 rows and 32 columns
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    elements: [[u8; MAX_DIM]; MAX_DIM],
+    rows: usize,
+    cols: usize,
+}
+
+impl Matrix {
+    /// Create matrix with `rows` rows and `cols` columns where every element is 0.
+    ///
+    /// # Panics
+    /// If `rows` or `cols` is greater than 32.
+    pub fn zero(rows: usize, cols: usize) -> Matrix {
+        assert!(rows <= MAX_DIM && cols <= MAX_DIM, "Matrix dimensions must not be greater than 32");
+        Matrix {
+            elements: [[0; MAX_DIM]; MAX_DIM],
+            rows,
+            cols,
+        }
+    }
+
+    /// Create matrix from its rows.
+    ///
+    /// Returns an error if the rows don't all have the same length, if there
+    /// are more than 32 rows or columns or if any element is greater than 31.
+    pub fn from_rows(rows: &[&[u8]]) -> Result<Matrix, UsageError> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        if rows.len() > MAX_DIM || cols > MAX_DIM || rows.iter().any(|row| row.len() != cols) {
+            return Err(invalid_dimensions());
+        }
+        if rows.iter().any(|row| row.iter().any(|&x| x > 31)) {
+            return Err(invalid_symbol());
+        }
+        let mut m = Matrix::zero(rows.len(), cols);
+        for (m_row, row) in m.elements.iter_mut().zip(rows) {
+            m_row[..cols].copy_from_slice(row);
+        }
+        Ok(m)
+    }
+
+    /// Create Vandermonde matrix with one row `[1, x, x^2, ...]` with `cols`
+    /// elements per point `x`.
+    ///
+    /// A square Vandermonde matrix is invertible if, and only if, the points
+    /// are distinct. Returns an error if there are more than 32 points or
+    /// columns or if any of the points is greater than 31.
+    pub fn vandermonde(points: &[u8], cols: usize) -> Result<Matrix, UsageError> {
+        if points.len() > MAX_DIM || cols > MAX_DIM {
+            return Err(invalid_dimensions());
+        }
+        if points.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        let mut m = Matrix::zero(points.len(), cols);
+        for (row, &x) in m.elements.iter_mut().zip(points) {
+            let mut power = 1;
+            for element in row[..cols].iter_mut() {
+                *element = power;
+                power = gf::mul(power, x);
+            }
+        }
+        Ok(m)
+    }
+
+    /// Create Cauchy matrix, whose element at row `i` and column `j` is
+    /// `1 / (xs[i] + ys[j])`.
+    ///
+    /// Every square submatrix of a Cauchy matrix is invertible. Returns an
+    /// error if the `xs` and `ys` together aren't all distinct, if there are
+    /// more than 32 of either or if any of them is greater than 31.
+    pub fn cauchy(xs: &[u8], ys: &[u8]) -> Result<Matrix, UsageError> {
+        if xs.len() > MAX_DIM || ys.len() > MAX_DIM {
+            return Err(invalid_dimensions());
+        }
+        if xs.iter().chain(ys).any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        let mut seen = [false; 32];
+        for &x in xs.iter().chain(ys) {
+            if seen[x as usize] {
+                return Err(invalid_points());
+            }
+            seen[x as usize] = true;
+        }
+        let mut m = Matrix::zero(xs.len(), ys.len());
+        for (row, &x) in m.elements.iter_mut().zip(xs) {
+            for (element, &y) in row.iter_mut().zip(ys) {
+                *element = gf::inverse(x ^ y);
+            }
+        }
+        Ok(m)
+    }
+
+    /// Number of rows
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Elements of row `row`
+    ///
+    /// # Panics
+    /// If `row` is out of range.
+    pub fn row(&self, row: usize) -> &[u8] {
+        assert!(row < self.rows, "Row out of range");
+        &self.elements[row][..self.cols]
+    }
+
+    /// Element at `row` and `col`
+    ///
+    /// # Panics
+    /// If `row` or `col` is out of range.
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.row(row)[col]
+    }
+
+    /// Set the element at `row` and `col` to `x`.
+    ///
+    /// # Panics
+    /// If `row` or `col` is out of range, or if `x` is greater than 31.
+    pub fn set(&mut self, row: usize, col: usize, x: u8) {
+        assert!(row < self.rows && col < self.cols, "Element out of range");
+        assert!(x <= 31, "Element must not be greater than 31");
+        self.elements[row][col] = x;
+    }
+
+    /// Create `n` by `n` identity matrix.
+    ///
+    /// # Panics
+    /// If `n` is greater than 32.
+    pub fn identity(n: usize) -> Matrix {
+        let mut m = Matrix::zero(n, n);
+        for i in 0..n {
+            m.elements[i][i] = 1;
+        }
+        m
+    }
+
+    /// Returns `true` if every element is 0.
+    pub fn is_zero(&self) -> bool {
+        (0..self.rows).all(|row| self.row(row).iter().all(|&x| x == 0))
+    }
+
+    /// Transposed matrix, with the rows and columns swapped
+    pub fn transpose(&self) -> Matrix {
+        let mut m = Matrix::zero(self.cols, self.rows);
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                m.elements[col][row] = self.elements[row][col];
+            }
+        }
+        m
+    }
+
+    /// Matrix sum `self + rhs`, which is also the difference
+    ///
+    /// Returns an error if `self` and `rhs` have different dimensions.
+    pub fn add(&self, rhs: &Matrix) -> Result<Matrix, UsageError> {
+        if self.rows != rhs.rows || self.cols != rhs.cols {
+            return Err(invalid_dimensions());
+        }
+        let mut m = *self;
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                m.elements[row][col] ^= rhs.elements[row][col];
+            }
+        }
+        Ok(m)
+    }
+
+    /// Matrix product `self · rhs`
+    ///
+    /// Returns an error if the number of columns of `self` doesn't match the
+    /// number of rows of `rhs`.
+    pub fn mul(&self, rhs: &Matrix) -> Result<Matrix, UsageError> {
+        if self.cols != rhs.rows {
+            return Err(invalid_dimensions());
+        }
+        let mut m = Matrix::zero(self.rows, rhs.cols);
+        for row in 0..self.rows {
+            for col in 0..rhs.cols {
+                m.elements[row][col] = (0..self.cols)
+                    .fold(0, |acc, i| acc ^ gf::mul(self.elements[row][i], rhs.elements[i][col]));
+            }
+        }
+        Ok(m)
+    }
+
+    /// Generator matrix of the dual code of the code generated by `self`
+    ///
+    /// `self` must be in systematic form `[I | P]`, and the result is
+    /// `[Pᵀ | I]`. Every row of the result is orthogonal to every row of `self`,
+    /// so the result is also a parity check matrix of the code generated by `self`.
+    ///
+    /// Returns an error if `self` doesn't start with an identity matrix.
+    pub fn dual(&self) -> Result<Matrix, UsageError> {
+        let k = self.rows;
+        if k > self.cols || (0..k).any(|row| (0..k).any(|col| self.elements[row][col] != (row == col) as u8)) {
+            return Err(invalid_dimensions());
+        }
+        let mut m = Matrix::zero(self.cols - k, self.cols);
+        for row in 0..self.cols - k {
+            for col in 0..k {
+                m.elements[row][col] = self.elements[col][k + row];
+            }
+            m.elements[row][k + row] = 1;
+        }
+        Ok(m)
+    }
+
+    /// Reduced row echelon form, found by Gaussian elimination
+    ///
+    /// The first nonzero element of each row is 1, it is the only nonzero
+    /// element of its column, and it is to the right of that of the row above.
+    /// Rows of zeros come last.
+    pub fn row_reduce(&self) -> Matrix {
+        let mut m = *self;
+        m.eliminate(&mut Matrix::zero(self.rows, 0));
+        m
+    }
+
+    /// Number of linearly independent rows, which is also the number of
+    /// linearly independent columns
+    pub fn rank(&self) -> usize {
+        let mut m = *self;
+        m.eliminate(&mut Matrix::zero(self.rows, 0))
+    }
+
+    /// Inverse matrix, or `None` if the matrix isn't square or isn't invertible
+    pub fn inverse(&self) -> Option<Matrix> {
+        if self.rows != self.cols {
+            return None;
+        }
+        self.solve(&Matrix::identity(self.rows))
+    }
+
+    /// Matrix `x` such that `self · x = rhs`
+    ///
+    /// Returns `None` if `self` isn't square or isn't invertible, so that
+    /// there isn't exactly one solution, or if `rhs` has a different number
+    /// of rows than `self`.
+    pub fn solve(&self, rhs: &Matrix) -> Option<Matrix> {
+        if self.rows != self.cols || rhs.rows != self.rows {
+            return None;
+        }
+        let mut m = *self;
+        let mut x = *rhs;
+        if m.eliminate(&mut x) != self.rows {
+            return None;
+        }
+        Some(x)
+    }
+
+    // Brings the matrix to reduced row echelon form, applying the same row
+    // operations to `other`, and returns the rank.
+    fn eliminate(&mut self, other: &mut Matrix) -> usize {
+        let mut rank = 0;
+        for col in 0..self.cols {
+            let pivot = match (rank..self.rows).find(|&row| self.elements[row][col] != 0) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            self.elements.swap(rank, pivot);
+            other.elements.swap(rank, pivot);
+
+            let scale = gf::inverse(self.elements[rank][col]);
+            for x in self.elements[rank].iter_mut().chain(other.elements[rank].iter_mut()) {
+                *x = gf::mul(*x, scale);
+            }
+
+            for row in (0..self.rows).filter(|&row| row != rank) {
+                let factor = self.elements[row][col];
+                if factor == 0 {
+                    continue;
+                }
+                for i in 0..MAX_DIM {
+                    self.elements[row][i] ^= gf::mul(factor, self.elements[rank][i]);
+                    other.elements[row][i] ^= gf::mul(factor, other.elements[rank][i]);
+                }
+            }
+            rank += 1;
+        }
+        rank
+    }
+
+    /// Formats the matrix as comma separated values, one line per row
+    ///
+    /// [`Display`](fmt::Display) formats it the same way, except that the
+    /// values are separated by spaces.
+    pub fn csv(&self) -> Csv<'_> {
+        Csv { matrix: self }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, separator: char) -> fmt::Result {
+        for row in 0..self.rows {
+            for (col, x) in self.row(row).iter().enumerate() {
+                if col > 0 {
+                    fmt::Write::write_char(f, separator)?;
+                }
+                write!(f, "{}", x)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, ' ')
+    }
+}
+
+/// Formats a [`Matrix`] as comma separated values
+#[derive(Debug, Copy, Clone)]
+pub struct Csv<'a> {
+    matrix: &'a Matrix,
+}
+
+impl fmt::Display for Csv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.matrix.write(f, ',')
+    }
+}
+
+impl fmt::Debug for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.rows).map(|row| self.row(row)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Matrix;
+    use crate::gf;
+
+    // Deterministic pseudo random matrix
+    fn random(rows: usize, cols: usize, seed: u32) -> Matrix {
+        let mut state = seed;
+        let mut m = Matrix::zero(rows, cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                m.set(row, col, (state >> 16) as u8 & 31);
+            }
+        }
+        m
+    }
+
+    #[test]
+    fn zero() {
+        let mut m = Matrix::zero(2, 3);
+        assert_eq!(2, m.rows());
+        assert_eq!(3, m.cols());
+        m.set(1, 2, 7);
+        assert_eq!(&[0, 0, 0], m.row(0));
+        assert_eq!(&[0, 0, 7], m.row(1));
+        assert_eq!(7, m.get(1, 2));
+    }
+
+    #[test]
+    fn mul() {
+        let mut a = Matrix::zero(2, 3);
+        a.set(0, 0, 1);
+        a.set(0, 2, 2);
+        a.set(1, 1, 3);
+        assert_eq!(a, Matrix::identity(2).mul(&a).unwrap());
+        assert_eq!(a, a.mul(&Matrix::identity(3)).unwrap());
+        assert_eq!(a, a.transpose().transpose());
+        assert_eq!(2, a.transpose().get(2, 0));
+
+        let p = a.mul(&a.transpose()).unwrap();
+        assert_eq!(&[1 ^ 4, 0], p.row(0));
+        assert_eq!(&[0, 5], p.row(1));
+        assert!(a.mul(&a).is_err());
+    }
+
+    #[test]
+    fn dual() {
+        let mut g = Matrix::zero(2, 4);
+        g.set(0, 0, 1);
+        g.set(1, 1, 1);
+        g.set(0, 2, 7);
+        g.set(0, 3, 9);
+        g.set(1, 2, 30);
+        let h = g.dual().unwrap();
+        assert_eq!(&[7, 30, 1, 0], h.row(0));
+        assert_eq!(&[9, 0, 0, 1], h.row(1));
+        assert!(g.mul(&h.transpose()).unwrap().is_zero());
+        assert!(h.dual().is_err());
+        assert_eq!(Matrix::identity(3), Matrix::zero(0, 3).dual().unwrap());
+    }
+
+    #[test]
+    fn from_rows() {
+        let m = Matrix::from_rows(&[&[1, 2, 3], &[4, 5, 6]]).unwrap();
+        assert_eq!((2, 3), (m.rows(), m.cols()));
+        assert_eq!(&[4, 5, 6], m.row(1));
+        assert!(Matrix::from_rows(&[&[1, 2, 3], &[4, 5]]).is_err());
+        assert!(Matrix::from_rows(&[&[32]]).is_err());
+        assert_eq!(Matrix::zero(0, 0), Matrix::from_rows(&[]).unwrap());
+    }
+
+    #[test]
+    fn algebra() {
+        for seed in 0..20 {
+            let a = random(5, 7, seed);
+            let b = random(7, 4, seed + 100);
+            let c = random(4, 6, seed + 200);
+            let d = random(7, 4, seed + 300);
+
+            let ab_c = a.mul(&b).unwrap().mul(&c).unwrap();
+            let a_bc = a.mul(&b.mul(&c).unwrap()).unwrap();
+            assert_eq!(ab_c, a_bc);
+
+            let left = a.mul(&b.add(&d).unwrap()).unwrap();
+            let right = a.mul(&b).unwrap().add(&a.mul(&d).unwrap()).unwrap();
+            assert_eq!(left, right);
+
+            assert_eq!(b.add(&d).unwrap(), d.add(&b).unwrap());
+            assert!(b.add(&b).unwrap().is_zero());
+            assert!(a.add(&b).is_err());
+
+            assert_eq!(a.mul(&b).unwrap().transpose(), b.transpose().mul(&a.transpose()).unwrap());
+        }
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(0, Matrix::zero(4, 5).rank());
+        assert_eq!(6, Matrix::identity(6).rank());
+
+        // The third row is the sum of the other two times a scalar
+        let mut m = Matrix::from_rows(&[&[1, 2, 3, 4], &[5, 6, 7, 8], &[0, 0, 0, 0]]).unwrap();
+        for col in 0..4 {
+            m.set(2, col, gf::mul(9, m.get(0, col) ^ m.get(1, col)));
+        }
+        assert_eq!(2, m.rank());
+        assert_eq!(2, m.transpose().rank());
+
+        let r = m.row_reduce();
+        assert_eq!(r, r.row_reduce());
+        assert_eq!(1, r.get(0, 0));
+        assert_eq!(0, r.get(1, 0));
+        assert_eq!(1, r.get(1, 1));
+        assert_eq!(&[0, 0, 0, 0], r.row(2));
+    }
+
+    #[test]
+    fn inverse() {
+        let mut invertible = 0;
+        for seed in 0..50 {
+            let a = random(6, 6, seed);
+            match a.inverse() {
+                Some(inv) => {
+                    invertible += 1;
+                    assert_eq!(6, a.rank());
+                    assert_eq!(Matrix::identity(6), a.mul(&inv).unwrap());
+                    assert_eq!(Matrix::identity(6), inv.mul(&a).unwrap());
+                    assert_eq!(a, inv.inverse().unwrap());
+
+                    let b = random(6, 2, seed + 1000);
+                    let x = a.solve(&b).unwrap();
+                    assert_eq!(b, a.mul(&x).unwrap());
+                }
+                None => assert!(a.rank() < 6),
+            }
+        }
+        // A random matrix over GF(32) is invertible with a probability of about 97%
+        assert!(invertible > 40);
+
+        assert!(Matrix::zero(3, 3).inverse().is_none());
+        assert!(random(3, 4, 0).inverse().is_none());
+        assert!(Matrix::identity(3).solve(&Matrix::zero(4, 1)).is_none());
+        assert_eq!(Some(Matrix::zero(0, 0)), Matrix::zero(0, 0).inverse());
+    }
+
+    #[test]
+    fn vandermonde() {
+        let points = [1, 2, 7, 19, 31, 0];
+        let v = Matrix::vandermonde(&points, 6).unwrap();
+        assert_eq!(&[1, 2, 4, 8, 16, 5], v.row(1));
+        assert_eq!(&[1, 0, 0, 0, 0, 0], v.row(5));
+        assert!(v.inverse().is_some());
+
+        let v = Matrix::vandermonde(&[3, 5, 3], 3).unwrap();
+        assert_eq!(2, v.rank());
+        assert!(Matrix::vandermonde(&[32], 3).is_err());
+    }
+
+    #[test]
+    fn cauchy() {
+        let xs = [1, 2, 3, 4, 5];
+        let ys = [6, 7, 8, 9, 10, 11];
+        let c = Matrix::cauchy(&xs, &ys).unwrap();
+        assert_eq!(gf::inverse(1 ^ 6), c.get(0, 0));
+
+        // Every square submatrix is invertible
+        for size in 1..=5 {
+            for first_row in 0..=5 - size {
+                for first_col in 0..=6 - size {
+                    let mut m = Matrix::zero(size, size);
+                    for row in 0..size {
+                        for col in 0..size {
+                            m.set(row, col, c.get(first_row + row, first_col + col));
+                        }
+                    }
+                    assert!(m.inverse().is_some());
+                }
+            }
+        }
+
+        assert!(Matrix::cauchy(&[1, 2], &[2, 3]).is_err());
+        assert!(Matrix::cauchy(&[1, 1], &[2, 3]).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn format() {
+        let mut m = Matrix::zero(2, 2);
+        m.set(0, 1, 31);
+        m.set(1, 0, 4);
+        assert_eq!("0 31\n4 0\n", m.to_string());
+        assert_eq!("0,31\n4,0\n", m.csv().to_string());
+    }
+}
@@ -0,0 +1,407 @@
+//! Conversion between 8-bit bytes and 5-bit symbols.
+//!
+//! Every 5 bytes are packed into 8 symbols. If the number of bytes is not a
+//! multiple of 5, the last symbol is padded with 0 bits. When unpacking, the
+//! bits left over after the last whole byte are treated as padding: there must
+//! be fewer than 5 of them and all of them must be 0, so that every sequence of
+//! bytes has exactly one valid packed form.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::encode;
+//! use reed_solomon_32::pack::{pack, plan, unpack, BitOrder};
+//!
+//! let payload = b"hello, world";
+//! let ecc = 8;
+//!
+//! let plan = plan(payload.len(), ecc).unwrap();
+//! let mut symbols = [0; 64];
+//! let symbols = &mut symbols[..plan.symbols()];
+//! pack(payload, BitOrder::MsbFirst, symbols).unwrap();
+//!
+//! let mut received = [0; 64];
+//! let mut received_len = 0;
+//! for chunk in symbols.chunks(plan.data_per_codeword()) {
+//!     let encoded = encode(chunk, ecc).unwrap();
+//!     received[received_len..received_len + chunk.len()].copy_from_slice(encoded.data());
+//!     received_len += chunk.len();
+//! }
+//! assert_eq!(plan.codewords(), 1);
+//!
+//! let mut bytes = [0; 12];
+//! unpack(&received[..received_len], BitOrder::MsbFirst, &mut bytes).unwrap();
+//! assert_eq!(payload, &bytes);
+//! ```
+
+use crate::err::{invalid_data_len, invalid_ecc, invalid_output_len, invalid_padding, invalid_symbol, UsageError};
+
+/// Order in which the bits of each byte are placed into symbols
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The most significant bit of the first byte becomes the most significant
+    /// bit of the first symbol.
+    MsbFirst,
+
+    /// The least significant bit of the first byte becomes the least significant
+    /// bit of the first symbol.
+    LsbFirst,
+}
+
+/// Number of symbols needed to pack `byte_len` bytes
+pub const fn symbols_len(byte_len: usize) -> usize {
+    // Each group of 5 bytes is 8 symbols, so this only overflows if the result
+    // doesn't fit in a `usize`
+    byte_len / 5 * 8 + (byte_len % 5 * 8 + 4) / 5
+}
+
+/// Number of bytes that `symbol_len` symbols unpack to
+pub const fn bytes_len(symbol_len: usize) -> usize {
+    symbol_len * 5 / 8
+}
+
+/// Packs `bytes` into `out` and returns the number of symbols written.
+pub fn pack(bytes: &[u8], order: BitOrder, out: &mut [u8]) -> Result<usize, UsageError> {
+    let len = symbols_len(bytes.len());
+    if out.len() < len {
+        return Err(invalid_output_len());
+    }
+    for (x, symbol) in out.iter_mut().zip(pack_iter(bytes.iter().copied(), order)) {
+        *x = symbol;
+    }
+    Ok(len)
+}
+
+/// Unpacks `symbols` into `out` and returns the number of bytes written.
+pub fn unpack(symbols: &[u8], order: BitOrder, out: &mut [u8]) -> Result<usize, UsageError> {
+    let len = bytes_len(symbols.len());
+    if out.len() < len {
+        return Err(invalid_output_len());
+    }
+    let mut written = 0;
+    for byte in unpack_iter(symbols.iter().copied(), order) {
+        out[written] = byte?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Returns an iterator that packs `bytes` into symbols.
+pub fn pack_iter<I: IntoIterator<Item = u8>>(bytes: I, order: BitOrder) -> Pack<I::IntoIter> {
+    Pack {
+        bytes: bytes.into_iter(),
+        order,
+        acc: 0,
+        bits: 0,
+    }
+}
+
+/// Returns an iterator that unpacks `symbols` into bytes.
+///
+/// An error is returned, and iteration stops, if one of the symbols is
+/// greater than 31 or if the padding at the end is invalid.
+pub fn unpack_iter<I: IntoIterator<Item = u8>>(symbols: I, order: BitOrder) -> Unpack<I::IntoIter> {
+    Unpack {
+        symbols: symbols.into_iter(),
+        order,
+        acc: 0,
+        bits: 0,
+        done: false,
+    }
+}
+
+/// Iterator returned by [`pack_iter`]
+#[derive(Debug, Clone)]
+pub struct Pack<I> {
+    bytes: I,
+    order: BitOrder,
+    acc: u32,
+    bits: u32,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Pack<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.bits < 5 {
+            match self.bytes.next() {
+                Some(byte) => {
+                    match self.order {
+                        BitOrder::MsbFirst => self.acc = (self.acc << 8) | byte as u32,
+                        BitOrder::LsbFirst => self.acc |= (byte as u32) << self.bits,
+                    }
+                    self.bits += 8;
+                }
+                None if self.bits == 0 => return None,
+                None => {
+                    // Pad the last symbol with 0 bits
+                    if self.order == BitOrder::MsbFirst {
+                        self.acc <<= 5 - self.bits;
+                    }
+                    self.bits = 5;
+                }
+            }
+        }
+
+        self.bits -= 5;
+        let symbol = match self.order {
+            BitOrder::MsbFirst => {
+                let symbol = self.acc >> self.bits;
+                self.acc &= (1 << self.bits) - 1;
+                symbol
+            }
+            BitOrder::LsbFirst => {
+                let symbol = self.acc & 31;
+                self.acc >>= 5;
+                symbol
+            }
+        };
+        Some(symbol as u8)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.bytes.size_hint();
+        let extra = |n: usize| {
+            n.checked_mul(8)
+                .and_then(|bits| bits.checked_add(self.bits as usize + 4))
+                .map(|bits| bits / 5)
+        };
+        (extra(lower).unwrap_or(usize::MAX), upper.and_then(extra))
+    }
+}
+
+/// Iterator returned by [`unpack_iter`]
+#[derive(Debug, Clone)]
+pub struct Unpack<I> {
+    symbols: I,
+    order: BitOrder,
+    acc: u32,
+    bits: u32,
+    done: bool,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Unpack<I> {
+    type Item = Result<u8, UsageError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if self.bits >= 8 {
+                self.bits -= 8;
+                let byte = match self.order {
+                    BitOrder::MsbFirst => {
+                        let byte = self.acc >> self.bits;
+                        self.acc &= (1 << self.bits) - 1;
+                        byte
+                    }
+                    BitOrder::LsbFirst => {
+                        let byte = self.acc & 0xff;
+                        self.acc >>= 8;
+                        byte
+                    }
+                };
+                return Some(Ok(byte as u8));
+            }
+
+            match self.symbols.next() {
+                Some(symbol) if symbol > 31 => {
+                    self.done = true;
+                    return Some(Err(invalid_symbol()));
+                }
+                Some(symbol) => {
+                    match self.order {
+                        BitOrder::MsbFirst => self.acc = (self.acc << 5) | symbol as u32,
+                        BitOrder::LsbFirst => self.acc |= (symbol as u32) << self.bits,
+                    }
+                    self.bits += 5;
+                }
+                None => {
+                    self.done = true;
+                    if self.bits >= 5 || self.acc != 0 {
+                        return Some(Err(invalid_padding()));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// How a byte payload is split into codewords, as computed by [`plan`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Plan {
+    symbols: usize,
+    ecc: u8,
+}
+
+impl Plan {
+    /// Number of data symbols that the payload packs to
+    pub fn symbols(&self) -> usize {
+        self.symbols
+    }
+
+    /// Maximum number of data symbols in each codeword
+    pub fn data_per_codeword(&self) -> usize {
+        crate::POLYNOMIAL_MAX_LENGTH - self.ecc as usize
+    }
+
+    /// Number of codewords needed for the payload
+    pub fn codewords(&self) -> usize {
+        let data = self.data_per_codeword();
+        match self.symbols % data {
+            0 => self.symbols / data,
+            _ => self.symbols / data + 1,
+        }
+    }
+
+    /// Number of data symbols in the last codeword
+    pub fn last_codeword_data(&self) -> usize {
+        match self.symbols % self.data_per_codeword() {
+            0 if self.symbols == 0 => 0,
+            0 => self.data_per_codeword(),
+            x => x,
+        }
+    }
+
+    /// Total number of symbols, including ECC symbols, across all codewords
+    pub fn total_symbols(&self) -> usize {
+        self.symbols + self.codewords() * self.ecc as usize
+    }
+}
+
+/// Plans how a payload of `byte_len` bytes is split into codewords with `ecc`
+/// error correcting symbols each.
+///
+/// Returns an error if the total number of symbols doesn't fit in a `usize`.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::pack::plan;
+///
+/// // 40 bytes pack into 64 symbols, which need 3 codewords of up to 23 data symbols
+/// let plan = plan(40, 8).unwrap();
+/// assert_eq!(64, plan.symbols());
+/// assert_eq!(3, plan.codewords());
+/// assert_eq!(18, plan.last_codeword_data());
+/// assert_eq!(88, plan.total_symbols());
+/// ```
+pub fn plan(byte_len: usize, ecc: u8) -> Result<Plan, UsageError> {
+    if ecc >= 31 {
+        return Err(invalid_ecc());
+    }
+    if byte_len / 5 > usize::MAX / 8 {
+        return Err(invalid_data_len());
+    }
+    let plan = Plan {
+        symbols: symbols_len(byte_len),
+        ecc,
+    };
+    plan.codewords()
+        .checked_mul(ecc as usize)
+        .and_then(|ecc_symbols| ecc_symbols.checked_add(plan.symbols))
+        .ok_or_else(invalid_data_len)?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_msb_first() {
+        let mut out = [0; 8];
+        assert_eq!(8, pack(&[0xff, 0x00, 0xa5, 0x5a, 0x81], BitOrder::MsbFirst, &mut out).unwrap());
+        // 11111 11100 00000 01010 01010 10110 10100 00001
+        assert_eq!([31, 28, 0, 10, 10, 22, 20, 1], out);
+
+        let mut out = [0; 2];
+        assert_eq!(2, pack(&[0xff], BitOrder::MsbFirst, &mut out).unwrap());
+        assert_eq!([31, 28], out);
+    }
+
+    #[test]
+    fn pack_lsb_first() {
+        let mut out = [0; 8];
+        assert_eq!(8, pack(&[0xff, 0x00, 0xa5, 0x5a, 0x81], BitOrder::LsbFirst, &mut out).unwrap());
+        assert_eq!([31, 7, 0, 10, 10, 13, 5, 16], out);
+
+        let mut out = [0; 2];
+        assert_eq!(2, pack(&[0xff], BitOrder::LsbFirst, &mut out).unwrap());
+        assert_eq!([31, 7], out);
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, 0x0f, 0xed, 0xcb];
+        for &order in &[BitOrder::MsbFirst, BitOrder::LsbFirst] {
+            for len in 0..=bytes.len() {
+                let mut symbols = [0; 18];
+                let packed_len = pack(&bytes[..len], order, &mut symbols).unwrap();
+                assert_eq!(symbols_len(len), packed_len);
+                assert!(symbols[..packed_len].iter().all(|&x| x <= 31));
+
+                let mut out = [0; 11];
+                let out_len = unpack(&symbols[..packed_len], order, &mut out).unwrap();
+                assert_eq!(&bytes[..len], &out[..out_len]);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_unpack() {
+        let mut out = [0; 8];
+        assert!(unpack(&[31, 29], BitOrder::MsbFirst, &mut out).is_err());
+        assert!(unpack(&[31, 15], BitOrder::LsbFirst, &mut out).is_err());
+        assert!(unpack(&[31, 28, 0], BitOrder::MsbFirst, &mut out).is_err());
+        assert!(unpack(&[31, 32], BitOrder::MsbFirst, &mut out).is_err());
+        assert!(unpack(&[31, 28, 0, 10], BitOrder::MsbFirst, &mut out[..1]).is_err());
+    }
+
+    #[test]
+    fn iterators() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        let packed = pack_iter(bytes.iter().copied(), BitOrder::MsbFirst);
+        assert_eq!((7, Some(7)), packed.size_hint());
+        let mut unpacked = unpack_iter(packed, BitOrder::MsbFirst);
+        for &byte in bytes.iter() {
+            assert_eq!(byte, unpacked.next().unwrap().unwrap());
+        }
+        assert!(unpacked.next().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn unbounded() {
+        let packed = pack_iter(core::iter::repeat(0xff), BitOrder::MsbFirst);
+        assert_eq!((usize::MAX, None), packed.size_hint());
+        assert_eq!(vec![31, 31, 31], packed.take(3).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn large_lengths() {
+        assert!(plan(usize::MAX, 0).is_err());
+        assert!(plan(usize::MAX / 4, 30).is_err());
+
+        let plan = plan(usize::MAX / 4, 0).unwrap();
+        assert_eq!(symbols_len(usize::MAX / 4), plan.symbols());
+        assert_eq!(plan.symbols(), plan.total_symbols());
+    }
+
+    #[test]
+    fn plans() {
+        let plan = plan(0, 4).unwrap();
+        assert_eq!(0, plan.codewords());
+        assert_eq!(0, plan.last_codeword_data());
+
+        let plan = super::plan(50, 4).unwrap();
+        assert_eq!(80, plan.symbols());
+        assert_eq!(27, plan.data_per_codeword());
+        assert_eq!(3, plan.codewords());
+        assert_eq!(26, plan.last_codeword_data());
+        assert_eq!(92, plan.total_symbols());
+
+        let plan = super::plan(5, 23).unwrap();
+        assert_eq!(1, plan.codewords());
+        assert_eq!(8, plan.last_codeword_data());
+
+        assert!(super::plan(1, 31).is_err());
+    }
+}
@@ -0,0 +1,514 @@
+use core::fmt;
+use core::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use crate::err::{invalid_degree, invalid_points, UsageError};
+use crate::Gf32;
+
+const MAX_LEN: usize = 64;
+
+/// A polynomial over GF(2^5) of degree at most 63
+///
+/// Leading zero coefficients are never stored, so the degree is always that of
+/// the highest nonzero coefficient and two polynomials compare equal if, and
+/// only if, all of their coefficients are equal.
+///
+/// Operations that would create a polynomial of degree greater than 63 panic.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::{Gf32, Poly};
+///
+/// let roots = [Gf32::exp(3), Gf32::exp(7)];
+/// let p = Poly::from_roots(&roots);
+/// assert_eq!(Some(2), p.degree());
+///
+/// let q = p * Poly::from_roots(&[Gf32::ONE]);
+/// assert_eq!(p, Poly::gcd(&p, &q));
+///
+/// let mut found = [Gf32::ZERO; 2];
+/// for (x, root) in found.iter_mut().zip(p.roots()) {
+///     *x = root;
+/// }
+/// assert_eq!([Gf32::exp(3), Gf32::exp(7)], found);
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Poly {
+    // Lowest degree first. Everything from `len` on is 0.
+    coefficients: [Gf32; MAX_LEN],
+    len: usize,
+}
+
+impl Poly {
+    /// The polynomial 0
+    pub const fn zero() -> Poly {
+        Poly {
+            coefficients: [Gf32::ZERO; MAX_LEN],
+            len: 0,
+        }
+    }
+
+    /// The polynomial 1
+    pub fn one() -> Poly {
+        Poly::constant(Gf32::ONE)
+    }
+
+    /// The polynomial `x`
+    pub fn x() -> Poly {
+        Poly::monomial(Gf32::ONE, 1)
+    }
+
+    /// The polynomial `c`
+    pub fn constant(c: Gf32) -> Poly {
+        Poly::monomial(c, 0)
+    }
+
+    /// The polynomial `c x^degree`
+    ///
+    /// # Panics
+    /// If `degree` is greater than 63.
+    pub fn monomial(c: Gf32, degree: usize) -> Poly {
+        assert!(degree < MAX_LEN, "Polynomial degree must not be greater than 63");
+        let mut p = Poly::zero();
+        p.coefficients[degree] = c;
+        p.trimmed()
+    }
+
+    /// Create polynomial from its coefficients, highest degree first.
+    ///
+    /// Returns an error if the degree would be greater than 63. Leading
+    /// zeros don't count towards the degree.
+    pub fn from_coefficients(coefficients: &[Gf32]) -> Result<Poly, UsageError> {
+        let zeros = coefficients.iter().take_while(|&&c| c == Gf32::ZERO).count();
+        let coefficients = &coefficients[zeros..];
+        if coefficients.len() > MAX_LEN {
+            return Err(invalid_degree());
+        }
+        let mut p = Poly::zero();
+        for (x, &c) in p.coefficients.iter_mut().zip(coefficients.iter().rev()) {
+            *x = c;
+        }
+        p.len = coefficients.len();
+        Ok(p)
+    }
+
+    /// The monic polynomial whose roots are `roots`, `(x - r_0)(x - r_1)...`
+    ///
+    /// # Panics
+    /// If there are more than 63 roots.
+    pub fn from_roots(roots: &[Gf32]) -> Poly {
+        roots.iter().fold(Poly::one(), |p, &r| p * Poly::from_linear(r))
+    }
+
+    /// The polynomial of degree less than `points.len()` that evaluates to
+    /// `values[i]` at `points[i]`, found by Lagrange interpolation.
+    ///
+    /// Returns an error if the points aren't distinct or if the number of
+    /// values doesn't match the number of points.
+    pub fn interpolate(points: &[Gf32], values: &[Gf32]) -> Result<Poly, UsageError> {
+        if points.len() != values.len() {
+            return Err(invalid_points());
+        }
+        for (i, x) in points.iter().enumerate() {
+            if points[..i].contains(x) {
+                return Err(invalid_points());
+            }
+        }
+
+        let all = Poly::from_roots(points);
+        let mut p = Poly::zero();
+        for (&point, &value) in points.iter().zip(values) {
+            if value == Gf32::ZERO {
+                continue;
+            }
+            // 0 at all of the points except `point`
+            let basis = all / Poly::from_linear(point);
+            p = p + basis * (value / basis.eval(point));
+        }
+        Ok(p)
+    }
+
+    /// Degree of the polynomial, or `None` for 0
+    pub fn degree(&self) -> Option<usize> {
+        self.len.checked_sub(1)
+    }
+
+    /// Returns `true` if the polynomial is 0.
+    pub fn is_zero(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Coefficient of `x^degree`
+    pub fn coefficient(&self, degree: usize) -> Gf32 {
+        self.coefficients.get(degree).copied().unwrap_or(Gf32::ZERO)
+    }
+
+    /// Coefficients, highest degree first. The polynomial 0 has none.
+    pub fn coefficients(&self) -> impl Iterator<Item = Gf32> + '_ {
+        self.coefficients[..self.len].iter().rev().copied()
+    }
+
+    /// Coefficient of the highest degree, or 0 for the polynomial 0
+    pub fn leading_coefficient(&self) -> Gf32 {
+        self.degree().map_or(Gf32::ZERO, |d| self.coefficients[d])
+    }
+
+    /// Returns `true` if the leading coefficient is 1.
+    pub fn is_monic(&self) -> bool {
+        self.leading_coefficient() == Gf32::ONE
+    }
+
+    /// The polynomial divided by its leading coefficient, so that it is monic.
+    /// The polynomial 0 stays 0.
+    pub fn monic(&self) -> Poly {
+        match self.leading_coefficient().inv() {
+            Some(inv) => *self * inv,
+            None => *self,
+        }
+    }
+
+    /// Evaluates the polynomial at `x`.
+    pub fn eval(&self, x: Gf32) -> Gf32 {
+        self.coefficients().fold(Gf32::ZERO, |acc, c| acc * x + c)
+    }
+
+    /// Formal derivative
+    ///
+    /// The field has characteristic 2, so the coefficients of even degree vanish.
+    pub fn derivative(&self) -> Poly {
+        let mut p = Poly::zero();
+        for i in (1..self.len).step_by(2) {
+            p.coefficients[i - 1] = self.coefficients[i];
+        }
+        p.trimmed()
+    }
+
+    /// `self(other(x))`
+    ///
+    /// # Panics
+    /// If the degree of the result would be greater than 63.
+    pub fn compose(&self, other: &Poly) -> Poly {
+        self.coefficients().fold(Poly::zero(), |acc, c| acc * *other + Poly::constant(c))
+    }
+
+    /// Quotient and remainder of dividing by `divisor`
+    ///
+    /// # Panics
+    /// If `divisor` is 0.
+    pub fn div_rem(&self, divisor: &Poly) -> (Poly, Poly) {
+        let divisor_degree = divisor.degree().expect("Division by zero");
+        let inv = divisor.leading_coefficient().inv().unwrap();
+
+        let mut quotient = Poly::zero();
+        let mut remainder = *self;
+        while remainder.len > divisor_degree {
+            let shift = remainder.len - 1 - divisor_degree;
+            let scale = remainder.leading_coefficient() * inv;
+            quotient.coefficients[shift] = scale;
+            for i in 0..=divisor_degree {
+                remainder.coefficients[shift + i] -= divisor.coefficients[i] * scale;
+            }
+            remainder = remainder.trimmed();
+        }
+        (quotient.trimmed(), remainder)
+    }
+
+    /// Monic greatest common divisor of `a` and `b`, which is 0 if both are 0
+    pub fn gcd(a: &Poly, b: &Poly) -> Poly {
+        let (mut a, mut b) = (*a, *b);
+        while !b.is_zero() {
+            let r = a % b;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// Monic greatest common divisor `g` of `a` and `b`, along with `s` and `t`
+    /// such that `s a + t b = g`, returned as `(g, s, t)`
+    pub fn extended_gcd(a: &Poly, b: &Poly) -> (Poly, Poly, Poly) {
+        let (mut r0, mut r1) = (*a, *b);
+        let (mut s0, mut s1) = (Poly::one(), Poly::zero());
+        let (mut t0, mut t1) = (Poly::zero(), Poly::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            r0 = r1;
+            r1 = r;
+            let s = s0 - q * s1;
+            s0 = s1;
+            s1 = s;
+            let t = t0 - q * t1;
+            t0 = t1;
+            t1 = t;
+        }
+        match r0.leading_coefficient().inv() {
+            Some(inv) => (r0 * inv, s0 * inv, t0 * inv),
+            None => (r0, s0, t0),
+        }
+    }
+
+    /// Elements of the field at which the polynomial is 0, in increasing order
+    /// of their values. Every element is a root of the polynomial 0.
+    pub fn roots(&self) -> impl Iterator<Item = Gf32> + '_ {
+        Gf32::elements().filter(move |&x| self.eval(x) == Gf32::ZERO)
+    }
+
+    /// Number of times `root` is a root of the polynomial, which is 0 if it
+    /// isn't one.
+    ///
+    /// # Panics
+    /// If the polynomial is 0.
+    pub fn multiplicity(&self, root: Gf32) -> usize {
+        assert!(!self.is_zero(), "The polynomial 0 has roots of any multiplicity");
+        let linear = Poly::from_linear(root);
+        let mut p = *self;
+        let mut count = 0;
+        loop {
+            let (q, r) = p.div_rem(&linear);
+            if !r.is_zero() {
+                return count;
+            }
+            p = q;
+            count += 1;
+        }
+    }
+
+    // x - r
+    fn from_linear(r: Gf32) -> Poly {
+        let mut p = Poly::x();
+        p.coefficients[0] = -r;
+        p
+    }
+
+    fn trimmed(mut self) -> Poly {
+        self.len = self.coefficients.iter().rposition(|&c| c != Gf32::ZERO).map_or(0, |i| i + 1);
+        self
+    }
+}
+
+impl Default for Poly {
+    fn default() -> Poly {
+        Poly::zero()
+    }
+}
+
+impl Add for Poly {
+    type Output = Poly;
+
+    fn add(mut self, rhs: Poly) -> Poly {
+        for (x, &y) in self.coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+            *x += y;
+        }
+        self.trimmed()
+    }
+}
+
+impl Sub for Poly {
+    type Output = Poly;
+
+    fn sub(mut self, rhs: Poly) -> Poly {
+        for (x, &y) in self.coefficients.iter_mut().zip(rhs.coefficients.iter()) {
+            *x -= y;
+        }
+        self.trimmed()
+    }
+}
+
+impl Neg for Poly {
+    type Output = Poly;
+
+    fn neg(self) -> Poly {
+        // Every coefficient is its own additive inverse
+        self
+    }
+}
+
+impl Mul for Poly {
+    type Output = Poly;
+
+    fn mul(self, rhs: Poly) -> Poly {
+        if self.is_zero() || rhs.is_zero() {
+            return Poly::zero();
+        }
+        assert!(self.len + rhs.len - 1 <= MAX_LEN, "Polynomial degree must not be greater than 63");
+        let mut p = Poly::zero();
+        for (i, &x) in self.coefficients[..self.len].iter().enumerate() {
+            for (j, &y) in rhs.coefficients[..rhs.len].iter().enumerate() {
+                p.coefficients[i + j] += x * y;
+            }
+        }
+        p.trimmed()
+    }
+}
+
+impl Mul<Gf32> for Poly {
+    type Output = Poly;
+
+    fn mul(mut self, rhs: Gf32) -> Poly {
+        for x in self.coefficients[..self.len].iter_mut() {
+            *x *= rhs;
+        }
+        self.trimmed()
+    }
+}
+
+impl Div for Poly {
+    type Output = Poly;
+
+    fn div(self, rhs: Poly) -> Poly {
+        self.div_rem(&rhs).0
+    }
+}
+
+impl Rem for Poly {
+    type Output = Poly;
+
+    fn rem(self, rhs: Poly) -> Poly {
+        self.div_rem(&rhs).1
+    }
+}
+
+impl fmt::Debug for Poly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Poly({})", self)
+    }
+}
+
+/// Formats the polynomial as for example `x^2 + 5x + 1`.
+impl fmt::Display for Poly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for degree in (0..self.len).rev() {
+            let c = self.coefficients[degree];
+            if c == Gf32::ZERO {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+            if c != Gf32::ONE || degree == 0 {
+                write!(f, "{}", c)?;
+            }
+            match degree {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{}", degree)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Poly;
+    use crate::Gf32;
+
+    fn poly(coefficients: &[u8]) -> Poly {
+        let mut c = [Gf32::ZERO; 64];
+        for (x, &y) in c.iter_mut().zip(coefficients) {
+            *x = Gf32::new(y).unwrap();
+        }
+        Poly::from_coefficients(&c[..coefficients.len()]).unwrap()
+    }
+
+    #[test]
+    fn degree() {
+        assert_eq!(None, Poly::zero().degree());
+        assert_eq!(Some(0), Poly::one().degree());
+        assert_eq!(Some(2), poly(&[0, 0, 3, 1, 7]).degree());
+        assert_eq!(poly(&[3, 1, 7]), poly(&[0, 0, 3, 1, 7]));
+        assert_eq!(Gf32::new(3).unwrap(), poly(&[3, 1, 7]).coefficient(2));
+        assert_eq!(Gf32::ZERO, poly(&[3, 1, 7]).coefficient(40));
+        assert_eq!(None, (poly(&[3, 1, 7]) - poly(&[3, 1, 7])).degree());
+        assert_eq!(Some(1), (poly(&[3, 1, 7]) + poly(&[3, 0, 0])).degree());
+        assert_eq!(Some(63), Poly::monomial(Gf32::ONE, 63).degree());
+        assert!(Poly::from_coefficients(&[Gf32::ONE; 65]).is_err());
+        assert!(Poly::from_coefficients(&[Gf32::ZERO; 65]).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn display() {
+        assert_eq!("x^2 + 5x + 1", format!("{}", poly(&[1, 5, 1])));
+        assert_eq!("7x^3 + 1", format!("{}", poly(&[7, 0, 0, 1])));
+        assert_eq!("0", format!("{}", Poly::zero()));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = poly(&[3, 1, 4, 1, 5]);
+        let b = poly(&[9, 2, 6]);
+        let (q, r) = a.div_rem(&b);
+        assert!(r.degree() < b.degree());
+        assert_eq!(a, q * b + r);
+        assert_eq!(q, a / b);
+        assert_eq!(r, a % b);
+
+        let x = Gf32::exp(5);
+        assert_eq!(a.eval(x) * b.eval(x), (a * b).eval(x));
+        assert_eq!(a.eval(b.eval(x)), a.compose(&b).eval(x));
+        assert!(a.monic().is_monic());
+        assert_eq!(a, a.monic() * Gf32::new(3).unwrap());
+
+        // d/dx (3x^4 + x^3 + 4x^2 + x + 5) = x^2 + 1 in characteristic 2
+        assert_eq!(poly(&[1, 0, 1]), a.derivative());
+    }
+
+    #[test]
+    #[should_panic]
+    fn degree_overflow() {
+        let _ = Poly::monomial(Gf32::ONE, 40) * Poly::monomial(Gf32::ONE, 40);
+    }
+
+    #[test]
+    fn gcd() {
+        let common = Poly::from_roots(&[Gf32::exp(1), Gf32::exp(2)]);
+        let a = common * poly(&[1, 0, 1, 1]);
+        let b = common * Poly::from_roots(&[Gf32::exp(9)]) * Gf32::exp(4);
+        assert_eq!(common, Poly::gcd(&a, &b));
+
+        let (g, s, t) = Poly::extended_gcd(&a, &b);
+        assert_eq!(common, g);
+        assert_eq!(g, s * a + t * b);
+
+        assert_eq!(Poly::zero(), Poly::gcd(&Poly::zero(), &Poly::zero()));
+        assert_eq!(common, Poly::gcd(&(common * Gf32::exp(3)), &Poly::zero()));
+    }
+
+    #[test]
+    fn roots() {
+        let roots = [Gf32::exp(3), Gf32::exp(3), Gf32::ZERO, Gf32::exp(20)];
+        let p = Poly::from_roots(&roots) * Gf32::exp(11);
+        let mut found = [Gf32::ZERO; 3];
+        assert_eq!(3, p.roots().count());
+        for (x, root) in found.iter_mut().zip(p.roots()) {
+            *x = root;
+        }
+        assert_eq!([Gf32::ZERO, Gf32::exp(3), Gf32::exp(20)], found);
+        assert_eq!(2, p.multiplicity(Gf32::exp(3)));
+        assert_eq!(1, p.multiplicity(Gf32::ZERO));
+        assert_eq!(0, p.multiplicity(Gf32::ONE));
+
+        // x^32 - x vanishes everywhere
+        let all = Poly::monomial(Gf32::ONE, 32) - Poly::x();
+        assert_eq!(32, all.roots().count());
+    }
+
+    #[test]
+    fn interpolate() {
+        let p = poly(&[7, 0, 3, 1, 30]);
+        let points = [Gf32::exp(0), Gf32::exp(4), Gf32::ZERO, Gf32::exp(17), Gf32::exp(30)];
+        let mut values = [Gf32::ZERO; 5];
+        for (y, &x) in values.iter_mut().zip(points.iter()) {
+            *y = p.eval(x);
+        }
+        assert_eq!(p, Poly::interpolate(&points, &values).unwrap());
+        assert_eq!(Poly::zero(), Poly::interpolate(&[], &[]).unwrap());
+
+        assert!(Poly::interpolate(&points[..4], &values).is_err());
+        assert!(Poly::interpolate(&[Gf32::ONE, Gf32::ONE], &values[..2]).is_err());
+    }
+}
@@ -0,0 +1,455 @@
+//! Decoding one step at a time.
+//!
+//! [`Decoder::correct`](crate::decoder::Decoder::correct) runs all of the steps
+//! of decoding at once. The types in this module run them one at a time, so that
+//! the result of each step can be inspected or compared with another
+//! implementation of the decoder:
+//!
+//! 1. [`Syndromes`]: the message evaluated at the roots of the code
+//! 2. [`ForneySyndromes`]: the syndromes with the erasures removed
+//! 3. [`ErrorLocator`]: the error locator polynomial from Berlekamp-Massey
+//! 4. [`Roots`]: the positions of the errors from a Chien search
+//! 5. [`ErrorEvaluator`]: the errata locator and error evaluator polynomials
+//! 6. [`Magnitudes`]: the values of the errata from the Forney algorithm
+//!
+//! Polynomials are given highest degree coefficient first. Symbol `p` of a
+//! message of `n` symbols is the coefficient of `x^(n - 1 - p)`, so its error
+//! locator is `α^(n - 1 - p)`, where `α` is 2.
+//!
+//! # Example
+//! ```rust
+//! use reed_solomon_32::encode;
+//! use reed_solomon_32::decoder::DECODER_4;
+//! use reed_solomon_32::stages::Syndromes;
+//!
+//! let mut msg = encode(&[1, 2, 3, 4], 4).unwrap();
+//! msg[1] ^= 5;
+//! msg[6] = 0;
+//!
+//! let syndromes = Syndromes::new(&DECODER_4, &msg).unwrap();
+//! assert!(!syndromes.is_zero());
+//!
+//! let forney = syndromes.forney(&[6]).unwrap();
+//! let locator = forney.error_locator().unwrap();
+//! assert_eq!(1, locator.degree());
+//!
+//! let roots = locator.roots().unwrap();
+//! assert_eq!(&[1], roots.error_positions());
+//! assert_eq!(&[1, 6], roots.positions());
+//!
+//! let magnitudes = roots.evaluator().magnitudes();
+//! assert_eq!(2, magnitudes.apply(&mut msg).unwrap());
+//! assert_eq!(&[1, 2, 3, 4], msg.data());
+//! ```
+
+use crate::decoder_impl::{check_message, Decoder};
+use crate::err::{invalid_codeword_len, CorrectionError, UsageError};
+use crate::gf;
+use crate::gf::poly::Polynom;
+
+const MAX_LEN: usize = crate::POLYNOMIAL_MAX_LENGTH;
+
+/// Syndromes of a message
+///
+/// Syndrome `j` is the message evaluated at `α^j`. They are all 0 if, and
+/// only if, the message is a valid codeword.
+#[derive(Debug, Copy, Clone)]
+pub struct Syndromes {
+    decoder: Decoder,
+    msg_len: usize,
+    synd: [u8; MAX_LEN - 1],
+}
+
+impl Syndromes {
+    /// Computes the syndromes of `msg` for the code of `decoder`.
+    pub fn new(decoder: &Decoder, msg: &[u8]) -> Result<Syndromes, UsageError> {
+        check_message(msg, decoder.ecc_len())?;
+        let padded = decoder.calc_syndromes(msg);
+        let mut synd = [0; MAX_LEN - 1];
+        synd[..decoder.ecc_len() as usize].copy_from_slice(&padded[1..]);
+        Ok(Syndromes {
+            decoder: *decoder,
+            msg_len: msg.len(),
+            synd,
+        })
+    }
+
+    /// Length of the message
+    pub fn msg_len(&self) -> usize {
+        self.msg_len
+    }
+
+    /// Syndromes, one per ECC symbol
+    pub fn values(&self) -> &[u8] {
+        &self.synd[..self.decoder.ecc_len() as usize]
+    }
+
+    /// Returns `true` if the message is a valid codeword.
+    pub fn is_zero(&self) -> bool {
+        self.values().iter().all(|&x| x == 0)
+    }
+
+    /// Removes the contribution of the erasures at `erase_pos` from the syndromes.
+    ///
+    /// Returns an error if any of the positions is outside of the message or
+    /// if there are more erasures than the decoder can correct.
+    pub fn forney(&self, erase_pos: &[u8]) -> Result<ForneySyndromes, CorrectionError> {
+        self.decoder.check_erasures(self.msg_len, Some(erase_pos))?;
+        let fsynd = self.decoder.forney_syndromes(&self.padded(), erase_pos, self.msg_len);
+
+        let mut forney = ForneySyndromes {
+            syndromes: *self,
+            fsynd: [0; MAX_LEN - 1],
+            erase_pos: [0; MAX_LEN - 1],
+            erase_len: erase_pos.len(),
+        };
+        forney.fsynd[..fsynd.len()].copy_from_slice(&fsynd);
+        forney.erase_pos[..erase_pos.len()].copy_from_slice(erase_pos);
+        Ok(forney)
+    }
+
+    // The decoder keeps a 0 in front of the syndromes
+    fn padded(&self) -> Polynom {
+        let mut synd = Polynom::with_length(self.values().len() + 1);
+        synd[1..].copy_from_slice(self.values());
+        synd
+    }
+}
+
+/// Syndromes of a message with the erasures removed
+///
+/// With `e` erasures, only the first `ecc - e` of them are left. They depend
+/// on the errors only, which is what lets the Berlekamp-Massey algorithm find
+/// the errors without knowing about the erasures.
+#[derive(Debug, Copy, Clone)]
+pub struct ForneySyndromes {
+    syndromes: Syndromes,
+    fsynd: [u8; MAX_LEN - 1],
+    erase_pos: [u8; MAX_LEN - 1],
+    erase_len: usize,
+}
+
+impl ForneySyndromes {
+    /// Forney syndromes, one per ECC symbol that isn't used by an erasure
+    pub fn values(&self) -> &[u8] {
+        &self.fsynd[..self.syndromes.values().len() - self.erase_len]
+    }
+
+    /// Positions of the erasures
+    pub fn erasures(&self) -> &[u8] {
+        &self.erase_pos[..self.erase_len]
+    }
+
+    /// Syndromes that the erasures were removed from
+    pub fn syndromes(&self) -> &Syndromes {
+        &self.syndromes
+    }
+
+    /// Finds the error locator polynomial with the Berlekamp-Massey algorithm.
+    ///
+    /// Returns an error if the locator has more roots than the decoder can correct.
+    pub fn error_locator(&self) -> Result<ErrorLocator, CorrectionError> {
+        let ecc_len = self.syndromes.values().len();
+        let err_loc = self.syndromes.decoder.find_error_locator(&self.fsynd[..ecc_len], None, self.erase_len)?;
+
+        let mut locator = ErrorLocator {
+            forney: *self,
+            err_loc: [0; MAX_LEN],
+            len: err_loc.len(),
+        };
+        locator.err_loc[..err_loc.len()].copy_from_slice(&err_loc);
+        Ok(locator)
+    }
+}
+
+/// Error locator polynomial
+///
+/// Its constant coefficient is 1, and it has one root per error, at the
+/// inverse of the locator of the error.
+#[derive(Debug, Copy, Clone)]
+pub struct ErrorLocator {
+    forney: ForneySyndromes,
+    err_loc: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl ErrorLocator {
+    /// Coefficients, highest degree first
+    pub fn coefficients(&self) -> &[u8] {
+        &self.err_loc[..self.len]
+    }
+
+    /// Degree of the polynomial, which is the number of errors
+    pub fn degree(&self) -> usize {
+        self.len - 1
+    }
+
+    /// Forney syndromes that the locator was computed from
+    pub fn forney_syndromes(&self) -> &ForneySyndromes {
+        &self.forney
+    }
+
+    /// Finds the positions of the errors with a Chien search.
+    ///
+    /// Returns an error if the number of roots inside of the message doesn't
+    /// match the degree of the locator, or if one of them is at an erasure.
+    pub fn roots(&self) -> Result<Roots, CorrectionError> {
+        let syndromes = &self.forney.syndromes;
+        let err_pos = syndromes.decoder.find_error_positions(self.coefficients(), self.forney.erasures(), syndromes.msg_len)?;
+
+        let mut roots = Roots {
+            locator: *self,
+            positions: [0; MAX_LEN],
+            errors: err_pos.len(),
+        };
+        roots.positions[..err_pos.len()].copy_from_slice(&err_pos);
+        roots.positions[err_pos.len()..err_pos.len() + self.forney.erase_len].copy_from_slice(self.forney.erasures());
+        Ok(roots)
+    }
+}
+
+/// Positions of the errata
+#[derive(Debug, Copy, Clone)]
+pub struct Roots {
+    locator: ErrorLocator,
+    // The errors, followed by the erasures
+    positions: [u8; MAX_LEN],
+    errors: usize,
+}
+
+impl Roots {
+    /// Positions of the errors, in the order the Chien search finds them
+    pub fn error_positions(&self) -> &[u8] {
+        &self.positions[..self.errors]
+    }
+
+    /// Positions of the errors followed by the positions of the erasures
+    pub fn positions(&self) -> &[u8] {
+        &self.positions[..self.errors + self.locator.forney.erase_len]
+    }
+
+    /// Error locator that the roots were found from
+    pub fn error_locator(&self) -> &ErrorLocator {
+        &self.locator
+    }
+
+    /// Computes the errata locator and the error evaluator polynomials.
+    pub fn evaluator(&self) -> ErrorEvaluator {
+        let syndromes = &self.locator.forney.syndromes;
+        let positions = self.positions();
+
+        let mut coef_pos = Polynom::with_length(positions.len());
+        for (x, &pos) in coef_pos.iter_mut().zip(positions) {
+            *x = (syndromes.msg_len - 1 - pos as usize) as u8;
+        }
+        let errata_loc = syndromes.decoder.find_errata_locator(&coef_pos);
+        let err_eval = syndromes.decoder.find_error_evaluator(&syndromes.padded().reverse(), &errata_loc, errata_loc.len() - 1);
+
+        let mut evaluator = ErrorEvaluator {
+            roots: *self,
+            errata_loc: [0; MAX_LEN],
+            err_eval: [0; MAX_LEN],
+            loc_len: errata_loc.len(),
+            eval_len: err_eval.len(),
+        };
+        evaluator.errata_loc[..errata_loc.len()].copy_from_slice(&errata_loc);
+        evaluator.err_eval[..err_eval.len()].copy_from_slice(&err_eval);
+        evaluator
+    }
+}
+
+/// Errata locator and error evaluator polynomials
+///
+/// The errata locator has one root per error or erasure. The error evaluator
+/// is the product of the errata locator and the syndrome polynomial
+/// `S_0 x + S_1 x^2 + ...`, modulo `x^(errata + 1)`.
+#[derive(Debug, Copy, Clone)]
+pub struct ErrorEvaluator {
+    roots: Roots,
+    errata_loc: [u8; MAX_LEN],
+    err_eval: [u8; MAX_LEN],
+    loc_len: usize,
+    eval_len: usize,
+}
+
+impl ErrorEvaluator {
+    /// Coefficients of the errata locator, highest degree first
+    pub fn errata_locator(&self) -> &[u8] {
+        &self.errata_loc[..self.loc_len]
+    }
+
+    /// Coefficients of the error evaluator, highest degree first
+    pub fn coefficients(&self) -> &[u8] {
+        &self.err_eval[..self.eval_len]
+    }
+
+    /// Positions of the errata that the polynomials were computed from
+    pub fn roots(&self) -> &Roots {
+        &self.roots
+    }
+
+    /// Computes the values of the errata with the Forney algorithm.
+    pub fn magnitudes(&self) -> Magnitudes {
+        let syndromes = &self.roots.locator.forney.syndromes;
+        let positions = self.roots.positions();
+        let magnitudes = syndromes.decoder.errata_magnitudes(syndromes.msg_len, &syndromes.padded(), positions);
+
+        let mut result = Magnitudes {
+            msg_len: syndromes.msg_len,
+            decoder: syndromes.decoder,
+            positions: [0; MAX_LEN],
+            magnitudes: [0; MAX_LEN],
+            len: positions.len(),
+        };
+        result.positions[..positions.len()].copy_from_slice(positions);
+        result.magnitudes[..magnitudes.len()].copy_from_slice(&magnitudes);
+        result
+    }
+}
+
+/// Values that the errata need to be XORed with to correct the message
+#[derive(Debug, Copy, Clone)]
+pub struct Magnitudes {
+    decoder: Decoder,
+    msg_len: usize,
+    positions: [u8; MAX_LEN],
+    magnitudes: [u8; MAX_LEN],
+    len: usize,
+}
+
+impl Magnitudes {
+    /// Positions of the errors followed by the positions of the erasures
+    pub fn positions(&self) -> &[u8] {
+        &self.positions[..self.len]
+    }
+
+    /// Magnitudes, in the same order as [`positions`](Magnitudes::positions)
+    pub fn values(&self) -> &[u8] {
+        &self.magnitudes[..self.len]
+    }
+
+    /// Corrects `msg`, which must be the message the syndromes were computed
+    /// from, and returns the number of errata.
+    ///
+    /// If the corrected message isn't a valid codeword, `msg` is left
+    /// unchanged and `TooManyErrors` is returned.
+    pub fn apply(&self, msg: &mut [u8]) -> Result<usize, CorrectionError> {
+        if msg.len() != self.msg_len {
+            return Err(invalid_codeword_len().into());
+        }
+        check_message(msg, self.decoder.ecc_len())?;
+
+        let xor = |msg: &mut [u8]| {
+            for (&pos, &magnitude) in self.positions().iter().zip(self.values()) {
+                msg[pos as usize] = gf::add(msg[pos as usize], magnitude);
+            }
+        };
+        xor(msg);
+        if self.decoder.is_corrupted_unchecked(msg) {
+            xor(msg);
+            return Err(CorrectionError::TooManyErrors);
+        }
+        Ok(self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode;
+    use crate::gf::poly_math::Eval;
+
+    #[test]
+    fn stages() {
+        let decoder = Decoder::new(8);
+        let encoded = encode(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 8).unwrap();
+
+        let syndromes = Syndromes::new(&decoder, &encoded).unwrap();
+        assert!(syndromes.is_zero());
+        assert_eq!(8, syndromes.values().len());
+        let locator = syndromes.forney(&[]).unwrap().error_locator().unwrap();
+        assert_eq!(0, locator.degree());
+
+        let mut msg = encoded;
+        msg[2] ^= 31;
+        msg[14] ^= 3;
+        msg[9] = 0;
+        msg[5] = 0;
+
+        let syndromes = Syndromes::new(&decoder, &msg).unwrap();
+        assert_eq!(17, syndromes.msg_len());
+        for (j, &s) in syndromes.values().iter().enumerate() {
+            assert_eq!(msg.eval(gf::EXP[j]), s);
+        }
+
+        let forney = syndromes.forney(&[9, 5]).unwrap();
+        assert_eq!(6, forney.values().len());
+        assert_eq!(&[9, 5], forney.erasures());
+
+        let locator = forney.error_locator().unwrap();
+        assert_eq!(2, locator.degree());
+        assert_eq!(Some(&1), locator.coefficients().last());
+
+        let roots = locator.roots().unwrap();
+        let mut errors = [0; 2];
+        errors.copy_from_slice(roots.error_positions());
+        errors.sort_unstable();
+        assert_eq!([2, 14], errors);
+        for &pos in roots.error_positions() {
+            let x = gf::pow(2, 16 - pos as i32);
+            assert_eq!(0, locator.coefficients().eval(gf::inverse(x)));
+        }
+        assert_eq!(&[9, 5], &roots.positions()[2..]);
+
+        let evaluator = roots.evaluator();
+        assert_eq!(5, evaluator.errata_locator().len());
+        for &pos in roots.positions() {
+            let x = gf::pow(2, 16 - pos as i32);
+            assert_eq!(0, evaluator.errata_locator().eval(gf::inverse(x)));
+        }
+
+        let magnitudes = evaluator.magnitudes();
+        assert_eq!(roots.positions(), magnitudes.positions());
+        let mut corrected = msg;
+        assert_eq!(4, magnitudes.apply(&mut corrected).unwrap());
+        assert_eq!(&encoded[..], &corrected[..]);
+
+        let locations = decoder.locate_errors(&msg, Some(&[9, 5])).unwrap();
+        for (&pos, &magnitude) in magnitudes.positions().iter().zip(magnitudes.values()) {
+            if magnitude != 0 {
+                let i = locations.positions().iter().position(|&x| x == pos).unwrap();
+                assert_eq!(locations.magnitudes()[i], magnitude);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let decoder = Decoder::new(4);
+        let mut msg = encode(&[1, 2, 3, 4], 4).unwrap();
+        let syndromes = Syndromes::new(&decoder, &msg).unwrap();
+        assert!(syndromes.forney(&[8]).is_err());
+        assert!(syndromes.forney(&[0, 1, 2, 3, 4]).is_err());
+        assert!(Syndromes::new(&decoder, &msg[..3]).is_err());
+
+        msg[0] ^= 1;
+        msg[1] ^= 1;
+        msg[2] ^= 1;
+        let syndromes = Syndromes::new(&decoder, &msg).unwrap();
+        assert!(syndromes.forney(&[]).unwrap().error_locator().and_then(|x| x.roots()).is_err());
+
+        let mut msg = encode(&[1, 2, 3, 4], 4).unwrap();
+        msg[0] ^= 1;
+        let magnitudes = Syndromes::new(&decoder, &msg).unwrap()
+            .forney(&[]).unwrap()
+            .error_locator().unwrap()
+            .roots().unwrap()
+            .evaluator()
+            .magnitudes();
+        assert!(magnitudes.apply(&mut msg[..7]).is_err());
+        let mut other = encode(&[4, 3, 2, 1], 4).unwrap();
+        let before = other;
+        assert!(magnitudes.apply(&mut other).is_err());
+        assert_eq!(&before[..], &other[..]);
+    }
+}
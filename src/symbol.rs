@@ -0,0 +1,144 @@
+use core::convert::TryFrom;
+use crate::alphabet::RFC4648;
+use crate::err::{invalid_symbol, UsageError};
+
+/// A single 5-bit symbol, guaranteed to be in the range [0, 31]
+///
+/// Functions that accept `&[Symbol]` don't need to validate their input,
+/// so converting data to symbols once up front avoids checking it again
+/// on every call.
+///
+/// # Example
+/// ```rust
+/// use reed_solomon_32::{encode_symbols, Symbol};
+///
+/// let data = Symbol::slice_from_bytes(&[0, 1, 2, 3]).unwrap();
+///
+/// let encoded = encode_symbols(data, 4).unwrap();
+///
+/// assert_eq!(&[0, 1, 2, 3], encoded.data());
+/// ```
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u8);
+
+impl Symbol {
+    /// Create symbol from `x` if it is in the range [0, 31]
+    pub const fn new(x: u8) -> Option<Symbol> {
+        if x > 31 {
+            None
+        } else {
+            Some(Symbol(x))
+        }
+    }
+
+    /// Value of the symbol
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+
+    /// Create symbol from a character of the RFC 4648 base32 alphabet.
+    ///
+    /// Lowercase characters are accepted as well. See the [`alphabet`](crate::alphabet)
+    /// module for other alphabets.
+    pub fn from_char(c: char) -> Option<Symbol> {
+        RFC4648.from_char(c).map(Symbol)
+    }
+
+    /// Character of the RFC 4648 base32 alphabet for the symbol
+    pub fn to_char(self) -> char {
+        RFC4648.to_char(self.0)
+    }
+
+    /// Reinterprets `bytes` as a slice of symbols after checking that
+    /// all of them are in the range [0, 31].
+    pub fn slice_from_bytes(bytes: &[u8]) -> Result<&[Symbol], UsageError> {
+        if bytes.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        // SAFETY: Symbol is repr(transparent) over u8 and all values were checked above.
+        Ok(unsafe { &*(bytes as *const [u8] as *const [Symbol]) })
+    }
+
+    /// Reinterprets `bytes` as a mutable slice of symbols after checking that
+    /// all of them are in the range [0, 31].
+    pub fn slice_from_bytes_mut(bytes: &mut [u8]) -> Result<&mut [Symbol], UsageError> {
+        if bytes.iter().any(|&x| x > 31) {
+            return Err(invalid_symbol());
+        }
+        // SAFETY: Symbol is repr(transparent) over u8 and all values were checked above.
+        Ok(unsafe { &mut *(bytes as *mut [u8] as *mut [Symbol]) })
+    }
+
+    /// Reinterprets a slice of symbols as bytes
+    pub fn slice_as_bytes(symbols: &[Symbol]) -> &[u8] {
+        // SAFETY: Symbol is repr(transparent) over u8.
+        unsafe { &*(symbols as *const [Symbol] as *const [u8]) }
+    }
+}
+
+impl TryFrom<u8> for Symbol {
+    type Error = UsageError;
+
+    fn try_from(x: u8) -> Result<Self, Self::Error> {
+        Symbol::new(x).ok_or_else(invalid_symbol)
+    }
+}
+
+impl TryFrom<char> for Symbol {
+    type Error = UsageError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Symbol::from_char(c).ok_or_else(invalid_symbol)
+    }
+}
+
+impl From<Symbol> for u8 {
+    fn from(symbol: Symbol) -> u8 {
+        symbol.0
+    }
+}
+
+impl From<Symbol> for char {
+    fn from(symbol: Symbol) -> char {
+        symbol.to_char()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryFrom;
+    use super::Symbol;
+
+    #[test]
+    fn try_from_u8() {
+        for x in 0..32 {
+            assert_eq!(x, u8::from(Symbol::try_from(x).unwrap()));
+        }
+        for x in 32..=255 {
+            assert!(Symbol::try_from(x).is_err());
+        }
+    }
+
+    #[test]
+    fn chars() {
+        for x in 0..32 {
+            let symbol = Symbol::new(x).unwrap();
+            assert_eq!(Some(symbol), Symbol::from_char(symbol.to_char()));
+            assert_eq!(Some(symbol), Symbol::from_char(symbol.to_char().to_ascii_lowercase()));
+        }
+        assert_eq!(Some(Symbol(26)), Symbol::from_char('2'));
+        assert_eq!(None, Symbol::from_char('1'));
+        assert_eq!(None, Symbol::from_char('='));
+    }
+
+    #[test]
+    fn slices() {
+        let bytes = [0, 5, 31, 17];
+        let symbols = Symbol::slice_from_bytes(&bytes).unwrap();
+        assert_eq!(&[Symbol(0), Symbol(5), Symbol(31), Symbol(17)], symbols);
+        assert_eq!(&bytes, Symbol::slice_as_bytes(symbols));
+
+        assert!(Symbol::slice_from_bytes(&[0, 32]).is_err());
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::unnecessary_mut_passed)]
+
 extern crate reed_solomon_32;
 
 use reed_solomon_32::encode;